@@ -67,6 +67,8 @@ pub struct Bone {
 pub struct Animation {
     /// The amount of frames the animation stores.
     pub frame_count: NonZeroUsize,
+    /// The frame rate of the animation if the file defines one.
+    pub frame_rate: Option<f32>,
     /// All channels in animation mapped to an index for a bone in the file skeleton.
     pub channels: IndexMap<usize, Channel>,
 }
@@ -75,6 +77,7 @@ impl Default for Animation {
     fn default() -> Self {
         Self {
             frame_count: NonZeroUsize::MIN,
+            frame_rate: None,
             channels: Default::default(),
         }
     }
@@ -87,6 +90,25 @@ pub struct Channel {
     pub location: IndexMap<usize, Vector3>,
     /// Rotational keyed data of the channel mapped to a frame.
     pub rotation: IndexMap<usize, Quaternion>,
+    /// Tangents of the locational keyed data mapped to a frame.
+    ///
+    /// Keys without tangents are interpolated linearly.
+    pub location_tangents: IndexMap<usize, Tangents<Vector3>>,
+    /// Tangents of the rotational keyed data mapped to a frame.
+    ///
+    /// Keys without tangents are interpolated spherically.
+    pub rotation_tangents: IndexMap<usize, Tangents<Quaternion>>,
+}
+
+/// The hermite tangents of a key in units per frame.
+///
+/// Bezier handles should be converted to hermite tangents when imported.
+#[derive(Debug, Clone, Copy)]
+pub struct Tangents<T> {
+    /// The tangent going into the key.
+    pub incoming: T,
+    /// The tangent going out of the key.
+    pub outgoing: T,
 }
 
 /// Data of a part for a file.
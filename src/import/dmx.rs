@@ -4,10 +4,13 @@ use datamodel::{
     deserialize,
 };
 use indexmap::{IndexMap, IndexSet};
-use std::{fs::File, io::BufReader, num::NonZeroUsize};
+use std::{fs::File, io::BufReader, num::NonZeroUsize, ops::Mul};
 use thiserror::Error as ThisError;
 
-use crate::{import::FileData, utilities::mathematics as Math};
+use crate::{
+    import::{FileData, Tangents},
+    utilities::mathematics as Math,
+};
 
 type Integer = i32;
 type IntegerArray = Vec<i32>;
@@ -16,6 +19,9 @@ type Vector2Array = Vec<Vector2>;
 type Vector3Array = Vec<Vector3>;
 type QuaternionArray = Vec<Quaternion>;
 
+/// The catmull rom interpolation types of a log key curve type.
+const CATMULL_ROM_CURVE_TYPES: [i32; 3] = [1, 11, 12];
+
 #[derive(Debug, ThisError)]
 pub enum ParseDMXError {
     #[error("Failed To Deserialize DMX File: {0}")]
@@ -430,6 +436,8 @@ pub fn load_dmx(mut file_buffer: BufReader<File>, file_name: String) -> Result<s
             let mut frame_rate = *animation_clip.frame_rate.get() as f32;
             if frame_rate <= 0.0 {
                 frame_rate = 30.0;
+            } else {
+                animation.frame_rate = Some(frame_rate);
             }
             let time_frame = animation_clip.time_frame.get().ok_or(ParseDMXError::MissingRequiredAttribute(
                 "timeFrame",
@@ -490,6 +498,8 @@ pub fn load_dmx(mut file_buffer: BufReader<File>, file_name: String) -> Result<s
                         }
                     };
 
+                    let smoothed_keys = load_smoothed_keys(layer, times.len())?;
+
                     if target_channel.eq("position") {
                         let log_layer = Vector3LogLayer::from_element(Element::clone(layer));
                         let values = log_layer.values.get();
@@ -507,6 +517,7 @@ pub fn load_dmx(mut file_buffer: BufReader<File>, file_name: String) -> Result<s
                             ))?;
                         let animation_channel = animation.channels.entry(bone).or_default();
 
+                        let mut smoothed_frames = Vec::new();
                         for (frame, time) in times.into_iter().enumerate() {
                             let time_frame = (time * frame_rate).ceil() as usize;
 
@@ -519,7 +530,14 @@ pub fn load_dmx(mut file_buffer: BufReader<File>, file_name: String) -> Result<s
                             animation_channel
                                 .location
                                 .insert(time_frame, Math::Vector3::new(position.x as f64, position.y as f64, position.z as f64));
+
+                            if smoothed_keys[frame] {
+                                smoothed_frames.push(time_frame);
+                            }
                         }
+
+                        let tangents = create_catmull_rom_tangents(&animation_channel.location, &smoothed_frames, |from, to| to - from);
+                        animation_channel.location_tangents.extend(tangents);
                         continue;
                     }
 
@@ -540,6 +558,7 @@ pub fn load_dmx(mut file_buffer: BufReader<File>, file_name: String) -> Result<s
                             ))?;
                         let animation_channel = animation.channels.entry(bone).or_default();
 
+                        let mut smoothed_frames = Vec::new();
                         for (frame, time) in times.into_iter().enumerate() {
                             let time_frame = (time * frame_rate).ceil() as usize;
 
@@ -553,7 +572,17 @@ pub fn load_dmx(mut file_buffer: BufReader<File>, file_name: String) -> Result<s
                                 time_frame,
                                 Math::Quaternion::from_xyzw(rotation.x as f64, rotation.y as f64, rotation.z as f64, rotation.w as f64),
                             );
+
+                            if smoothed_keys[frame] {
+                                smoothed_frames.push(time_frame);
+                            }
                         }
+
+                        // The neighboring rotations are moved to the same hemisphere so the tangent takes the shortest path.
+                        let tangents = create_catmull_rom_tangents(&animation_channel.rotation, &smoothed_frames, |from, to| {
+                            if from.dot(to) < 0.0 { -to - from } else { to - from }
+                        });
+                        animation_channel.rotation_tangents.extend(tangents);
                     }
                 }
             }
@@ -565,6 +594,63 @@ pub fn load_dmx(mut file_buffer: BufReader<File>, file_name: String) -> Result<s
     Ok(file_data)
 }
 
+/// Returns which keys of a log layer use a catmull rom curve, no keys do if the layer has no curve types.
+fn load_smoothed_keys(layer: &Element, key_count: usize) -> Result<Vec<bool>, ParseDMXError> {
+    let Some(curve_types_attribute) = layer.get_attribute("curvetypes") else {
+        return Ok(vec![false; key_count]);
+    };
+
+    let raw_curve_types = curve_types_attribute.get_inner();
+    let datamodel::attribute::AttributeValue::IntegerArray(curve_types) = &*raw_curve_types else {
+        return Err(ParseDMXError::MissingRequiredAttribute("curvetypes", "IntegerArray", *layer.get_id()));
+    };
+
+    if curve_types.is_empty() {
+        return Ok(vec![false; key_count]);
+    }
+
+    if curve_types.len() != key_count {
+        return Err(ParseDMXError::MissedMatchedArray("curvetypes", "times", *layer.get_id()));
+    }
+
+    // The curve type has the interpolation into the key in the low byte and out of the key in the next byte.
+    Ok(curve_types
+        .iter()
+        .map(|curve_type| CATMULL_ROM_CURVE_TYPES.contains(&(curve_type & 0xFF)) || CATMULL_ROM_CURVE_TYPES.contains(&((curve_type >> 8) & 0xFF)))
+        .collect())
+}
+
+/// Creates the tangents of the smoothed keys from the neighboring keys, in units per frame.
+fn create_catmull_rom_tangents<T>(keys: &IndexMap<usize, T>, smoothed_frames: &[usize], difference: impl Fn(T, T) -> T) -> Vec<(usize, Tangents<T>)>
+where
+    T: Copy + Mul<f64, Output = T>,
+{
+    let mut key_frames = keys.keys().copied().collect::<Vec<_>>();
+    key_frames.sort_unstable();
+
+    let mut tangents = Vec::with_capacity(smoothed_frames.len());
+    for &frame in smoothed_frames {
+        let key_index = key_frames.partition_point(|&key_frame| key_frame < frame);
+        let previous_frame = key_frames[key_index.saturating_sub(1)];
+        let next_frame = key_frames[(key_index + 1).min(key_frames.len() - 1)];
+
+        if previous_frame == next_frame {
+            continue;
+        }
+
+        let tangent = difference(keys[&previous_frame], keys[&next_frame]) * (1.0 / (next_frame - previous_frame) as f64);
+        tangents.push((
+            frame,
+            Tangents {
+                incoming: tangent,
+                outgoing: tangent,
+            },
+        ));
+    }
+
+    tangents
+}
+
 #[derive(Clone, ElementClass)]
 #[class_name("DmElement")]
 struct FileModelData {
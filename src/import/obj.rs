@@ -38,6 +38,7 @@ pub fn load_obj(file_buffer: BufReader<File>, file_name: String) -> Result<super
             file_name,
             super::Animation {
                 frame_count: NonZero::new(1).unwrap(),
+                frame_rate: None,
                 channels: IndexMap::new(),
             },
         )]),
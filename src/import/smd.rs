@@ -504,6 +504,7 @@ pub fn load_smd(file_buffer: BufReader<File>, file_name: String) -> Result<super
 
     let mut animation = super::Animation {
         frame_count: NonZero::new(frames.len()).unwrap(),
+        frame_rate: None,
         channels: IndexMap::with_capacity(import_bones.len()),
     };
 
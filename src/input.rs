@@ -2,7 +2,7 @@ use std::path::PathBuf;
 
 use indexmap::{IndexMap, IndexSet};
 
-use crate::utilities::mathematics::Vector3;
+use crate::utilities::{keyvalues::KeyValue, mathematics::Vector3};
 
 #[derive(Clone, Debug, Default)]
 pub struct SourceInput {
@@ -10,10 +10,34 @@ pub struct SourceInput {
     pub model_name: String,
     /// The path to where the mdl is exported.
     pub export_path: Option<PathBuf>,
+    /// The directories the game searches for the model materials.
+    pub material_paths: Vec<String>,
+    /// The material names to replace from the source files.
+    pub material_renames: Vec<MaterialRename>,
+    /// The game content directory used to validate the model materials.
+    pub content_directory: Option<PathBuf>,
+    /// The properties written to the model header.
+    pub model_properties: ModelProperties,
+    /// The key values written to the model for the game to read.
+    pub keyvalues: Vec<KeyValue>,
+    /// The settings used to split and compress all animations.
+    pub animation_settings: AnimationSettings,
     pub model_groups: Vec<ModelGroup>,
+    /// The rules to rename the bones of every source file, applied in order.
+    pub bone_renames: Vec<BoneRename>,
+    /// The differences allowed between the same bone in different source files.
+    pub skeleton_tolerances: SkeletonTolerances,
+    /// The bones driven by other bones, imported from a VRD file.
+    pub procedural_bones: Vec<ProceduralBone>,
+    /// The controllers the game uses to move bones, like turret yaw or door open.
+    pub bone_controllers: Vec<BoneController>,
     pub bone_properties: Vec<BoneProperty>,
     pub animation_identifier_generator: usize,
     pub animations: Vec<Animation>,
+    /// The values the game sets to blend sequences, like aim yaw or move speed.
+    pub pose_parameters: Vec<PoseParameter>,
+    /// The named bone weights sequences use to mask bones.
+    pub weight_lists: Vec<WeightList>,
     pub sequences: Vec<Sequence>,
     pub flex_key_identifier_generator: usize,
     pub flex_keys: Vec<FlexKey>,
@@ -21,6 +45,117 @@ pub struct SourceInput {
     pub flex_controllers: Vec<FlexController>,
 }
 
+/// A struct to define the properties of the model.
+#[derive(Clone, Debug)]
+pub struct ModelProperties {
+    /// The physics surface property of the model.
+    pub surface_property: String,
+    /// The model is solid to traces.
+    pub contents_solid: bool,
+    /// The model is a grate, can not be set with solid.
+    pub contents_grate: bool,
+    /// The model is solid to monsters.
+    pub contents_monster: bool,
+    /// The model can be climbed.
+    pub contents_ladder: bool,
+    /// The hitboxes are generated from the mesh.
+    pub auto_generated_hitbox: bool,
+    /// The model is only rendered in the opaque pass.
+    pub force_opaque: bool,
+    /// The model is rendered in the opaque and translucent pass.
+    pub translucent_two_pass: bool,
+    /// The model is a static prop.
+    pub static_prop: bool,
+    /// The model is marked obsolete.
+    pub obsolete: bool,
+    /// The model does not fade out at a distance.
+    pub no_forced_fade: bool,
+    /// The model always cross fades phonemes.
+    pub force_phoneme_cross_fade: bool,
+    /// The model ambient lighting is boosted.
+    pub ambient_boost: bool,
+    /// The model does not cast shadows.
+    pub do_not_cast_shadows: bool,
+    /// The model casts shadows from the textures.
+    pub cast_texture_shadows: bool,
+    /// Specifies if the eye position is defined.
+    pub define_eye_position: bool,
+    /// The position of the eyes relative to the model origin.
+    pub eye_position: Vector3,
+    /// Specifies if the illumination position is defined.
+    pub define_illumination_position: bool,
+    /// The position the model is lit from, the center of the model if not defined.
+    pub illumination_position: Vector3,
+    /// The mass of the model in kilograms.
+    pub mass: f64,
+    /// Specifies if the constant directional light dot is defined.
+    pub define_constant_directional_light_dot: bool,
+    /// The constant directional light dot from 0 to 1.
+    pub constant_directional_light_dot: f64,
+}
+
+impl Default for ModelProperties {
+    fn default() -> Self {
+        Self {
+            surface_property: String::from("default"),
+            contents_solid: true,
+            contents_grate: false,
+            contents_monster: false,
+            contents_ladder: false,
+            auto_generated_hitbox: true,
+            force_opaque: true,
+            translucent_two_pass: false,
+            static_prop: false,
+            obsolete: false,
+            no_forced_fade: false,
+            force_phoneme_cross_fade: false,
+            ambient_boost: false,
+            do_not_cast_shadows: false,
+            cast_texture_shadows: false,
+            define_eye_position: false,
+            eye_position: Vector3::ZERO,
+            define_illumination_position: false,
+            illumination_position: Vector3::ZERO,
+            mass: 0.0,
+            define_constant_directional_light_dot: false,
+            constant_directional_light_dot: 0.0,
+        }
+    }
+}
+
+/// A struct to define how animations are split and compressed.
+#[derive(Clone, Debug)]
+pub struct AnimationSettings {
+    /// The amount of frames in each animation section.
+    pub section_frames: usize,
+    /// The frame count an animation needs to be split into sections.
+    pub section_threshold: usize,
+    /// Specifies if near constant animation axes are flattened.
+    pub compress: bool,
+    /// The largest position error allowed when compressing.
+    pub position_tolerance: f64,
+    /// The largest rotation error in degrees allowed when compressing.
+    pub rotation_tolerance: f64,
+    /// Specifies if the animation data is written to an external ani file.
+    pub external_animation_blocks: bool,
+    /// The size in kilobytes of each block in the ani file.
+    pub animation_block_size: usize,
+}
+
+impl Default for AnimationSettings {
+    fn default() -> Self {
+        Self {
+            section_frames: 30,
+            section_threshold: 120,
+            compress: false,
+            position_tolerance: 0.01,
+            rotation_tolerance: 0.05,
+            external_animation_blocks: false,
+            animation_block_size: 32,
+        }
+    }
+}
+
 pub trait NamedData {
     fn get_name(&self) -> &String;
     fn set_name(&mut self, name: String);
@@ -89,6 +224,15 @@ impl Default for Model {
 
 implement_named_data! {Model}
 
+/// A struct to define a replacement name for a source file material.
+#[derive(Clone, Debug, Default)]
+pub struct MaterialRename {
+    /// The name of the material in the source files.
+    pub source_name: String,
+    /// The name the material will have in the model.
+    pub model_name: String,
+}
+
 #[derive(Clone, Debug, Default)]
 pub struct Flex {
     pub assigned_flex_key: Option<usize>,
@@ -135,6 +279,87 @@ impl Default for FlexController {
 
 implement_named_data! {FlexController}
 
+/// A struct to define how much the same bone can differ between source files.
+#[derive(Clone, Debug)]
+pub struct SkeletonTolerances {
+    /// The largest distance allowed between the bone locations.
+    pub location: f64,
+    /// The largest angle in degrees allowed between the bone rotations.
+    pub rotation: f64,
+}
+
+impl Default for SkeletonTolerances {
+    fn default() -> Self {
+        Self { location: 0.01, rotation: 0.1 }
+    }
+}
+
+/// A rule to rename the bones of the source files.
+#[derive(Clone, Debug, Default)]
+pub struct BoneRename {
+    /// How the pattern is matched to the bone name.
+    pub kind: BoneRenameKind,
+    /// The name, prefix or regex to match.
+    pub pattern: String,
+    /// The text that replaces the match, an empty replacement strips the match.
+    pub replacement: String,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum BoneRenameKind {
+    /// The whole bone name must match the pattern.
+    #[default]
+    Exact,
+    /// The bone name must start with the pattern.
+    Prefix,
+    /// All matches of the regex pattern are replaced, the replacement can use capture groups.
+    Regex,
+}
+
+/// A controller that offsets a bone on an axis from a game set value.
+#[derive(Clone, Debug)]
+pub struct BoneController {
+    /// The name of the bone that is moved.
+    pub bone: String,
+    /// The axis the bone is moved or rotated on.
+    pub axis: BoneControllerAxis,
+    /// The value when the controller is at its minimum, in degrees for rotation axes.
+    pub start: f64,
+    /// The value when the controller is at its maximum, in degrees for rotation axes.
+    pub end: f64,
+    /// The value of the controller when the bone is at rest.
+    pub rest: f64,
+    /// The controller index the game sets, from 0 to 3.
+    pub index: usize,
+    /// Specifies if the controller is driven by the mouth of the model instead of the controller index.
+    pub mouth: bool,
+}
+
+impl Default for BoneController {
+    fn default() -> Self {
+        Self {
+            bone: String::new(),
+            axis: BoneControllerAxis::default(),
+            start: -90.0,
+            end: 90.0,
+            rest: 0.0,
+            index: 0,
+            mouth: false,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum BoneControllerAxis {
+    X,
+    Y,
+    Z,
+    XR,
+    YR,
+    #[default]
+    ZR,
+}
+
 #[derive(Clone, Debug)]
 pub struct BoneProperty {
     /// The unique name of the bone to define.
@@ -152,12 +377,22 @@ pub struct BoneProperty {
     /// The rotation of the bone relative to the parent.
     /// These are as pitch, yaw, and roll for compatibility.
     pub rotation: Vector3,
+    /// The bone is never collapsed, even if nothing uses it.
+    pub keep_bone: bool,
+    /// The bone is always collapsed, even if it is used.
+    pub force_collapse: bool,
+    /// Specifies if the bone is a jiggle bone.
+    pub jiggle_bone: bool,
+    /// The spring properties of the jiggle bone.
+    pub jiggle: JiggleBone,
     pub ik_chain: bool,
     pub ik_chain_name: String, // TODO: Make this use check name conflicts.
     pub ik_chain_knee: Vector3,
     pub ik_chain_auto_play: bool,
-    pub ik_chain_position_lock: f32,
-    pub ik_chain_rotation_lock: f32,
+    /// The weight the foot is kept at its locked position, written as is like studiomdl.
+    pub ik_chain_position_weight: f32,
+    /// The weight the foot is kept at its locked rotation, written as is like studiomdl.
+    pub ik_chain_rotation_weight: f32,
 }
 
 impl Default for BoneProperty {
@@ -170,18 +405,164 @@ impl Default for BoneProperty {
             location: Default::default(),
             define_rotation: Default::default(),
             rotation: Default::default(),
+            keep_bone: false,
+            force_collapse: false,
+            jiggle_bone: false,
+            jiggle: Default::default(),
             ik_chain: false,
             ik_chain_name: String::from("New Ik Chain"),
             ik_chain_knee: Vector3::ZERO,
             ik_chain_auto_play: false,
-            ik_chain_position_lock: 1.0,
-            ik_chain_rotation_lock: 0.9,
+            ik_chain_position_weight: 1.0,
+            ik_chain_rotation_weight: 0.1,
         }
     }
 }
 
 implement_named_data! {BoneProperty}
 
+/// A struct to define a procedural spring bone that jiggles with the movement of its parent.
+#[derive(Clone, Debug)]
+pub struct JiggleBone {
+    /// The bone only moves at the base spring, else the tip flexes.
+    pub rigid: bool,
+    /// The distance from the bone base to the tip along the bone.
+    pub length: f64,
+    /// The mass of the tip, pulled by gravity.
+    pub tip_mass: f64,
+    pub yaw_stiffness: f64,
+    pub yaw_damping: f64,
+    pub pitch_stiffness: f64,
+    pub pitch_damping: f64,
+    /// The tip can stretch and compress along the bone.
+    pub allow_length_flex: bool,
+    pub along_stiffness: f64,
+    pub along_damping: f64,
+    /// Specifies if the deflection of the tip is limited.
+    pub define_angle_limit: bool,
+    /// The largest deflection of the tip in degrees.
+    pub angle_limit: f64,
+    /// Specifies if the yaw of the tip is limited.
+    pub define_yaw_constraint: bool,
+    /// The yaw limits in degrees.
+    pub minimum_yaw: f64,
+    pub maximum_yaw: f64,
+    pub yaw_friction: f64,
+    pub yaw_bounce: f64,
+    /// Specifies if the pitch of the tip is limited.
+    pub define_pitch_constraint: bool,
+    /// The pitch limits in degrees.
+    pub minimum_pitch: f64,
+    pub maximum_pitch: f64,
+    pub pitch_friction: f64,
+    pub pitch_bounce: f64,
+    /// Specifies if the base of the bone moves on a spring.
+    pub base_spring: bool,
+    pub base_mass: f64,
+    pub base_stiffness: f64,
+    pub base_damping: f64,
+    /// The movement limits of the base along the left, up and forward axes.
+    pub base_minimum: Vector3,
+    pub base_maximum: Vector3,
+    pub base_friction: Vector3,
+}
+
+impl Default for JiggleBone {
+    fn default() -> Self {
+        Self {
+            rigid: false,
+            length: 10.0,
+            tip_mass: 0.0,
+            yaw_stiffness: 100.0,
+            yaw_damping: 0.0,
+            pitch_stiffness: 100.0,
+            pitch_damping: 0.0,
+            allow_length_flex: false,
+            along_stiffness: 100.0,
+            along_damping: 0.0,
+            define_angle_limit: false,
+            angle_limit: 0.0,
+            define_yaw_constraint: false,
+            minimum_yaw: 0.0,
+            maximum_yaw: 0.0,
+            yaw_friction: 0.0,
+            yaw_bounce: 0.0,
+            define_pitch_constraint: false,
+            minimum_pitch: 0.0,
+            maximum_pitch: 0.0,
+            pitch_friction: 0.0,
+            pitch_bounce: 0.0,
+            base_spring: false,
+            base_mass: 0.0,
+            base_stiffness: 100.0,
+            base_damping: 0.0,
+            base_minimum: Vector3::splat(-100.0),
+            base_maximum: Vector3::splat(100.0),
+            base_friction: Vector3::ZERO,
+        }
+    }
+}
+
+/// A bone that is driven by other bones.
+#[derive(Clone, Debug)]
+pub enum ProceduralBone {
+    /// The bone blends between poses as the control bone rotation matches triggers.
+    Quaternion(QuaternionProceduralBone),
+    /// The bone points at another bone.
+    AimAt(AimAtProceduralBone),
+}
+
+impl ProceduralBone {
+    /// Returns the name of the bone that is driven.
+    pub fn bone(&self) -> &str {
+        match self {
+            Self::Quaternion(quaternion_bone) => &quaternion_bone.bone,
+            Self::AimAt(aim_at_bone) => &aim_at_bone.bone,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct QuaternionProceduralBone {
+    /// The name of the driven bone.
+    pub bone: String,
+    /// The name of the parent of the driven bone.
+    pub parent: String,
+    /// The name of the bone whose rotation is matched to the triggers.
+    pub control: String,
+    /// The location added to the location of every trigger.
+    pub base_position: Vector3,
+    pub triggers: Vec<ProceduralTrigger>,
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct ProceduralTrigger {
+    /// The angle in degrees the control bone must be within the trigger to have influence.
+    pub tolerance: f64,
+    /// The rotation in degrees of the control bone.
+    pub trigger: Vector3,
+    /// The rotation in degrees of the driven bone.
+    pub rotation: Vector3,
+    /// The location of the driven bone.
+    pub position: Vector3,
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct AimAtProceduralBone {
+    /// The name of the driven bone.
+    pub bone: String,
+    /// The name of the parent of the driven bone.
+    pub parent: String,
+    /// The name of the bone to point at.
+    pub aim: String,
+    /// The axis of the driven bone that points at the aim bone.
+    pub aim_vector: Vector3,
+    /// The axis of the driven bone that points up.
+    pub up_vector: Vector3,
+    /// The location of the driven bone relative to the parent.
+    pub base_position: Vector3,
+}
+
 /// A struct to define an animation for the model.
 #[derive(Clone, Debug)]
 pub struct Animation {
@@ -193,6 +574,52 @@ pub struct Animation {
     pub source_animation: usize,
     /// A unique values used by sequences to find the correct animation as animations order and name can be changed.
     pub animation_identifier: usize,
+    /// Specifies if the frame rate is defined, else the source file frame rate is used.
+    pub define_frame_rate: bool,
+    /// The frames per second of the animation.
+    pub frame_rate: f32,
+    /// Specifies if the frame range is defined, else all frames are used.
+    pub define_frame_range: bool,
+    /// The first frame of the source animation to use.
+    pub start_frame: usize,
+    /// The last frame of the source animation to use.
+    pub end_frame: usize,
+    /// The frames are played in reverse.
+    pub reverse: bool,
+    /// The animation loops back to the first frame.
+    pub looping: bool,
+    /// Specifies if the animation is made a delta by subtracting a frame of an animation.
+    pub define_subtract: bool,
+    /// The identifier of the animation to subtract.
+    pub subtract_animation: usize,
+    /// The frame of the subtract animation to subtract.
+    pub subtract_frame: usize,
+    /// Extracts the root motion along the X axis.
+    pub motion_x: bool,
+    /// Extracts the root motion along the Y axis.
+    pub motion_y: bool,
+    /// Extracts the root motion along the Z axis.
+    pub motion_z: bool,
+    /// Extracts the root rotation around the X axis.
+    pub motion_x_rotation: bool,
+    /// Extracts the root rotation around the Y axis.
+    pub motion_y_rotation: bool,
+    /// Extracts the root rotation around the Z axis.
+    pub motion_z_rotation: bool,
+    /// Specifies if the motion is extracted in segments, else linearly over the whole animation.
+    pub motion_piecewise: bool,
+    /// The amount of frames in each motion segment.
+    pub motion_segment_frames: usize,
+    /// Specifies if the sections are defined, else the project animation settings are used.
+    pub define_sections: bool,
+    /// The amount of frames in each animation section.
+    pub section_frames: usize,
+    /// The frame count the animation needs to be split into sections.
+    pub section_threshold: usize,
+    /// The source bones that drive model bones with a different name.
+    pub bone_mappings: Vec<BoneMapping>,
+    /// The difference between the source and model rest pose is removed from the animation.
+    pub retarget_rest_pose: bool,
 }
 
 impl Default for Animation {
@@ -202,19 +629,91 @@ impl Default for Animation {
             source_file_path: Default::default(),
             source_animation: Default::default(),
             animation_identifier: Default::default(),
+            define_frame_rate: false,
+            frame_rate: 30.0,
+            define_frame_range: false,
+            start_frame: 0,
+            end_frame: 0,
+            reverse: false,
+            looping: false,
+            define_subtract: false,
+            subtract_animation: Default::default(),
+            subtract_frame: 0,
+            motion_x: false,
+            motion_y: false,
+            motion_z: false,
+            motion_x_rotation: false,
+            motion_y_rotation: false,
+            motion_z_rotation: false,
+            motion_piecewise: false,
+            motion_segment_frames: 10,
+            define_sections: false,
+            section_frames: 30,
+            section_threshold: 120,
+            bone_mappings: Vec::new(),
+            retarget_rest_pose: false,
         }
     }
 }
 
 implement_named_data! {Animation}
 
+/// A struct to map a bone of an animation source to a bone of the model.
+#[derive(Clone, Debug, Default)]
+pub struct BoneMapping {
+    /// The name of the bone in the source file.
+    pub source_bone: String,
+    /// The name of the bone in the model.
+    pub target_bone: String,
+}
+
 /// A struct the define a sequence for a model.
 #[derive(Clone, Debug)]
 pub struct Sequence {
     /// The unique name of the sequence.
     pub name: String,
-    /// A N by N grid of animations used by the sequence.
+    /// A N by N grid of animations used by the sequence, indexed by row then column.
     pub animations: Vec<Vec<usize>>,
+    /// The pose parameters that blend the grid, the first blends the columns and the second blends the rows.
+    pub blend_axes: [BlendAxis; 2],
+    /// The activity the game uses to find the sequence, if empty the sequence has no activity.
+    pub activity: String,
+    /// The chance of the sequence being picked over other sequences with the same activity.
+    pub activity_weight: i32,
+    /// Specifies if the sequence loops, this is always set if the first animation loops.
+    pub looping: bool,
+    /// Specifies if the sequence snaps to the first frame instead of blending in.
+    pub snap: bool,
+    /// Specifies if the sequence is added to other sequences, this is always set if the first animation is a delta.
+    pub delta: bool,
+    /// Specifies if the sequence always plays as a layer.
+    pub auto_play: bool,
+    /// Specifies if the sequence cycle is driven by the game time.
+    pub realtime: bool,
+    /// Specifies if the sequence is hidden from model viewers.
+    pub hidden: bool,
+    /// The time in seconds to blend in the sequence.
+    pub fade_in_time: f64,
+    /// The time in seconds to blend out the sequence.
+    pub fade_out_time: f64,
+    /// Specifies if the sequence transitions between nodes.
+    pub define_transition: bool,
+    /// The node the sequence starts at.
+    pub entry_node: String,
+    /// The node the sequence ends at, if empty the entry node is used.
+    pub exit_node: String,
+    /// Specifies if the sequence can be played in reverse to transition from the exit node to the entry node.
+    pub reverse_transition: bool,
+    /// The events the game fires as the sequence plays.
+    pub events: Vec<SequenceEvent>,
+    /// The sequences played as layers on top of the sequence.
+    pub auto_layers: Vec<AutoLayer>,
+    /// The name of the weight list of the sequence, if empty all bones have full weight.
+    pub weight_list: String,
+    /// The rules that place the ik chains while the sequence plays.
+    pub ik_rules: Vec<IkRule>,
+    /// The ik chains that are locked in place while the sequence plays.
+    pub ik_locks: Vec<IkLock>,
 }
 
 impl Default for Sequence {
@@ -222,8 +721,211 @@ impl Default for Sequence {
         Self {
             name: String::from("New Sequence"),
             animations: Default::default(),
+            blend_axes: Default::default(),
+            activity: String::new(),
+            activity_weight: 1,
+            looping: false,
+            snap: false,
+            delta: false,
+            auto_play: false,
+            realtime: false,
+            hidden: false,
+            fade_in_time: 0.2,
+            fade_out_time: 0.2,
+            define_transition: false,
+            entry_node: String::new(),
+            exit_node: String::new(),
+            reverse_transition: false,
+            events: Vec::new(),
+            auto_layers: Vec::new(),
+            weight_list: String::new(),
+            ik_rules: Vec::new(),
+            ik_locks: Vec::new(),
         }
     }
 }
 
+/// A sequence that is always played as a layer while the sequence plays.
+#[derive(Clone, Debug, Default)]
+pub struct AutoLayer {
+    /// The name of the sequence to layer.
+    pub sequence: String,
+    /// Specifies if the layer is blended in and out, otherwise the layer is always at full weight.
+    pub blend: bool,
+    /// The frame or pose parameter value the layer starts blending in.
+    pub start: f64,
+    /// The frame or pose parameter value the layer is at full weight.
+    pub peak: f64,
+    /// The frame or pose parameter value the layer starts blending out.
+    pub tail: f64,
+    /// The frame or pose parameter value the layer is fully blended out.
+    pub end: f64,
+    /// The blend in and out is eased with a spline.
+    pub spline: bool,
+    /// The layer is blended out as the layer sequence fades in.
+    pub cross_fade: bool,
+    /// The layer is not blended, the weight is set by the cycle of the layer.
+    pub no_blend: bool,
+    /// The layer is applied in local space.
+    pub local: bool,
+    /// Specifies if the blend is driven by a pose parameter instead of the cycle.
+    pub define_pose_parameter: bool,
+    /// The name of the pose parameter that drives the blend.
+    pub pose_parameter: String,
+}
+
+/// A rule that places an ik chain during a range of a sequence.
+#[derive(Clone, Debug, Default)]
+pub struct IkRule {
+    /// The name of the ik chain the rule is applied to.
+    pub chain: String,
+    pub kind: IkRuleKind,
+    /// The name of the bone the chain touches, used by touch rules.
+    pub bone: String,
+    /// Specifies if the frame range is defined, else the rule is applied to all frames.
+    pub define_range: bool,
+    /// The frame the rule starts blending in.
+    pub start: usize,
+    /// The frame the rule is at full weight.
+    pub peak: usize,
+    /// The frame the rule starts blending out.
+    pub tail: usize,
+    /// The frame the rule is fully blended out.
+    pub end: usize,
+    /// The frame the foot is planted on the ground, used by footstep rules.
+    pub contact: usize,
+    /// The height of the foot above the ground.
+    pub height: f64,
+    /// The radius of the foot used to find the ground.
+    pub radius: f64,
+    /// The height of the ground relative to the model.
+    pub floor: f64,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum IkRuleKind {
+    /// The chain is placed relative to another bone.
+    Touch,
+    /// The chain is placed on the ground.
+    #[default]
+    Footstep,
+    /// The chain is released from the previous rule.
+    Release,
+    /// The chain is unlatched from the previous rule.
+    Unlatch,
+}
+
+/// An ik chain that is locked to where the chain was before the sequence played.
+#[derive(Clone, Debug)]
+pub struct IkLock {
+    /// The name of the ik chain to lock.
+    pub chain: String,
+    /// The weight the foot is kept at its locked position, written as is like studiomdl.
+    pub position_weight: f32,
+    /// The weight the foot is kept at its locked rotation, written as is like studiomdl.
+    pub rotation_weight: f32,
+}
+
+impl Default for IkLock {
+    fn default() -> Self {
+        Self {
+            chain: String::new(),
+            position_weight: 1.0,
+            rotation_weight: 0.1,
+        }
+    }
+}
+
+/// An event the game fires when a sequence reaches a point, like a footstep or a sound.
+#[derive(Clone, Debug, Default)]
+pub struct SequenceEvent {
+    /// Specifies if the event is placed by cycle, otherwise it is placed by the frame of the first animation.
+    pub use_cycle: bool,
+    /// The frame of the first animation the event fires at.
+    pub frame: usize,
+    /// The cycle from 0 to 1 the event fires at.
+    pub cycle: f64,
+    /// The number or the name of the event.
+    pub event: String,
+    /// The options passed to the event, like the name of the sound to play.
+    pub options: String,
+}
+
+/// A pose parameter that blends an axis of a sequence grid.
+#[derive(Clone, Debug, Default)]
+pub struct BlendAxis {
+    /// The name of the pose parameter, if empty the axis is not blended.
+    pub parameter: String,
+    /// Specifies if the range is defined, otherwise the range of the pose parameter is used.
+    pub define_range: bool,
+    /// The pose parameter value at the first animation of the axis.
+    pub start: f64,
+    /// The pose parameter value at the last animation of the axis.
+    pub end: f64,
+}
+
+/// A list of bone weights, bones not in the list use the weight of the parent and root bones not in the list have no weight.
+#[derive(Clone, Debug)]
+pub struct WeightList {
+    /// The unique name of the weight list.
+    pub name: String,
+    pub weights: Vec<BoneWeight>,
+}
+
+impl Default for WeightList {
+    fn default() -> Self {
+        Self {
+            name: String::from("New Weight List"),
+            weights: Default::default(),
+        }
+    }
+}
+
+implement_named_data! {WeightList}
+
+#[derive(Clone, Debug)]
+pub struct BoneWeight {
+    /// The name of the bone.
+    pub bone: String,
+    /// The weight from 0 to 1 of the bone and the children without a weight.
+    pub weight: f64,
+}
+
+impl Default for BoneWeight {
+    fn default() -> Self {
+        Self {
+            bone: String::new(),
+            weight: 1.0,
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct PoseParameter {
+    /// The unique name of the pose parameter.
+    pub name: String,
+    /// The minimum value of the pose parameter.
+    pub start: f64,
+    /// The maximum value of the pose parameter.
+    pub end: f64,
+    /// Specifies if the value wraps around.
+    pub define_loop: bool,
+    /// The range the value wraps around at.
+    pub loop_range: f64,
+}
+
+impl Default for PoseParameter {
+    fn default() -> Self {
+        Self {
+            name: String::from("New Pose Parameter"),
+            start: -1.0,
+            end: 1.0,
+            define_loop: false,
+            loop_range: 360.0,
+        }
+    }
+}
+
+implement_named_data! {PoseParameter}
+
 implement_named_data! {Sequence}
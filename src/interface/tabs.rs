@@ -4,7 +4,9 @@ use std::sync::{Arc, atomic::AtomicBool};
 mod animations;
 mod bone_properties;
 mod flexing;
+mod keyvalues;
 mod log;
+mod materials;
 mod model_groups;
 mod overview;
 mod sequences;
@@ -13,6 +15,8 @@ pub enum UniqueTabs {
     Overview,
     Log,
     ModelGroups,
+    Materials,
+    KeyValues,
     Flexing,
     BoneProperties,
     Animations,
@@ -43,6 +47,8 @@ impl<'a> egui_dock::TabViewer for TabViewer<'a> {
             UniqueTabs::Overview => String::from("Overview").into(),
             UniqueTabs::Log => String::from("Log").into(),
             UniqueTabs::ModelGroups => String::from("Model Groups").into(),
+            UniqueTabs::Materials => String::from("Materials").into(),
+            UniqueTabs::KeyValues => String::from("Key Values").into(),
             UniqueTabs::Flexing => String::from("Flexing").into(),
             UniqueTabs::BoneProperties => String::from("Bone Properties").into(),
             UniqueTabs::Animations => String::from("Animations").into(),
@@ -55,6 +61,8 @@ impl<'a> egui_dock::TabViewer for TabViewer<'a> {
             UniqueTabs::Overview => self.render_overview(ui),
             UniqueTabs::Log => self.render_log(ui),
             UniqueTabs::ModelGroups => self.render_model_groups(ui),
+            UniqueTabs::Materials => self.render_materials(ui),
+            UniqueTabs::KeyValues => self.render_keyvalues(ui),
             UniqueTabs::Flexing => self.render_flexing(ui),
             UniqueTabs::BoneProperties => self.render_bone_properties(ui),
             UniqueTabs::Animations => self.render_animation(ui),
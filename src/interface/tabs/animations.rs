@@ -2,7 +2,7 @@ use std::sync::Arc;
 
 use crate::{
     import::{FileData, FileManager, FileStatus, SUPPORTED_FILES},
-    input::Animation,
+    input::{Animation, AnimationSettings},
     interface::{
         fix_naming_conflicts,
         icons::{IconType, icon},
@@ -32,6 +32,8 @@ impl<'a> TabViewer<'a> {
         egui::CentralPanel::default().show(ui, |ui| {
             ui.heading("Animations");
             ui.separator();
+            render_animation_settings(ui, &mut self.input_data.animation_settings);
+            ui.separator();
 
             if let Some(active_animation_index) = selected_animation {
                 egui::ScrollArea::vertical().auto_shrink([false; 2]).show(ui, |ui| {
@@ -44,6 +46,13 @@ impl<'a> TabViewer<'a> {
                         fix_naming_conflicts(&mut self.input_data.animations, active_animation_index);
                     }
 
+                    let animation_names = self
+                        .input_data
+                        .animations
+                        .iter()
+                        .map(|animation| (animation.animation_identifier, animation.name.clone()))
+                        .collect::<Vec<_>>();
+
                     let active_animation = &mut self.input_data.animations[active_animation_index];
                     render_file_selection(ui, self.loaded_files, active_animation);
 
@@ -53,7 +62,10 @@ impl<'a> TabViewer<'a> {
                         render_file_status(ui, &file_status, active_animation);
 
                         if let FileStatus::Loaded(file_data) = file_status {
-                            render_options(ui, file_data, active_animation);
+                            render_options(ui, Arc::clone(&file_data), active_animation);
+                            render_retarget_options(ui, &file_data, active_animation);
+                            render_subtract_options(ui, &animation_names, active_animation);
+                            render_motion_options(ui, active_animation);
                         }
                     }
                 });
@@ -64,6 +76,48 @@ impl<'a> TabViewer<'a> {
     }
 }
 
+fn render_animation_settings(ui: &mut egui::Ui, animation_settings: &mut AnimationSettings) {
+    egui::CollapsingHeader::new("Animation Settings").show(ui, |ui| {
+        ui.horizontal(|ui| {
+            ui.label("Section Frames:");
+            ui.add(egui::DragValue::new(&mut animation_settings.section_frames).range(1..=usize::MAX));
+            ui.label("Section Threshold:");
+            ui.add(egui::DragValue::new(&mut animation_settings.section_threshold));
+        });
+
+        ui.horizontal(|ui| {
+            ui.checkbox(&mut animation_settings.compress, "Compress");
+            if animation_settings.compress {
+                ui.label("Position Tolerance:");
+                ui.add(
+                    egui::DragValue::new(&mut animation_settings.position_tolerance)
+                        .range(0.0..=f64::MAX)
+                        .speed(0.001),
+                );
+                ui.label("Rotation Tolerance:");
+                ui.add(
+                    egui::DragValue::new(&mut animation_settings.rotation_tolerance)
+                        .range(0.0..=180.0)
+                        .speed(0.01)
+                        .suffix("°"),
+                );
+            }
+        });
+
+        ui.horizontal(|ui| {
+            ui.checkbox(&mut animation_settings.external_animation_blocks, "External Animation Blocks");
+            if animation_settings.external_animation_blocks {
+                ui.label("Block Size:");
+                ui.add(
+                    egui::DragValue::new(&mut animation_settings.animation_block_size)
+                        .range(1..=usize::MAX)
+                        .suffix(" KB"),
+                );
+            }
+        });
+    });
+}
+
 fn render_file_selection(ui: &mut egui::Ui, file_manager: &mut FileManager, active_animation: &mut Animation) {
     if ui.button("Select Model File…").clicked()
         && let Some(path) = rfd::FileDialog::new()
@@ -112,4 +166,144 @@ fn render_options(ui: &mut egui::Ui, file_data: Arc<FileData>, active_animation:
                 ui.selectable_value(&mut active_animation.source_animation, source_animation_index, source_animation_name);
             }
         });
+
+    let source_animation = &file_data.animations[active_animation.source_animation];
+    let source_frame_count = source_animation.frame_count.get();
+    ui.label(format!("Source Frame Count: {source_frame_count}"));
+
+    ui.horizontal(|ui| {
+        ui.checkbox(&mut active_animation.define_frame_rate, "");
+        ui.label("Frame Rate: ");
+        if active_animation.define_frame_rate {
+            ui.add(egui::DragValue::new(&mut active_animation.frame_rate).range(1.0..=f32::MAX).speed(0.1));
+        } else {
+            ui.label(format!("{} (Source)", source_animation.frame_rate.unwrap_or(30.0)));
+        }
+    });
+
+    ui.horizontal(|ui| {
+        ui.checkbox(&mut active_animation.define_frame_range, "");
+        ui.label("Frame Range: ");
+        if active_animation.define_frame_range {
+            let last_frame = source_frame_count - 1;
+            ui.label("Start:");
+            ui.add(egui::DragValue::new(&mut active_animation.start_frame).range(0..=last_frame));
+            ui.label("End:");
+            ui.add(egui::DragValue::new(&mut active_animation.end_frame).range(active_animation.start_frame..=last_frame));
+        } else {
+            ui.label("All Frames");
+        }
+    });
+
+    ui.checkbox(&mut active_animation.reverse, "Reverse");
+    ui.checkbox(&mut active_animation.looping, "Looping");
+
+    ui.horizontal(|ui| {
+        ui.checkbox(&mut active_animation.define_sections, "");
+        ui.label("Sections: ");
+        if active_animation.define_sections {
+            ui.label("Frames:");
+            ui.add(egui::DragValue::new(&mut active_animation.section_frames).range(1..=usize::MAX));
+            ui.label("Threshold:");
+            ui.add(egui::DragValue::new(&mut active_animation.section_threshold));
+        } else {
+            ui.label("Project Settings");
+        }
+    });
+}
+
+fn render_subtract_options(ui: &mut egui::Ui, animation_names: &[(usize, String)], active_animation: &mut Animation) {
+    ui.horizontal(|ui| {
+        ui.checkbox(&mut active_animation.define_subtract, "");
+        ui.label("Subtract: ");
+        if !active_animation.define_subtract {
+            ui.label("Not A Delta");
+            return;
+        }
+
+        let selected_name = match animation_names
+            .iter()
+            .find(|(identifier, _)| *identifier == active_animation.subtract_animation)
+        {
+            Some((_, name)) => name.as_str(),
+            None => {
+                active_animation.subtract_animation = active_animation.animation_identifier;
+                "Select Animation…"
+            }
+        };
+
+        egui::ComboBox::from_id_salt("Subtract Animation")
+            .selected_text(selected_name)
+            .show_ui(ui, |ui| {
+                for (identifier, name) in animation_names {
+                    ui.selectable_value(&mut active_animation.subtract_animation, *identifier, name);
+                }
+            });
+
+        ui.label("Frame:");
+        ui.add(egui::DragValue::new(&mut active_animation.subtract_frame));
+    });
+}
+
+fn render_retarget_options(ui: &mut egui::Ui, file_data: &FileData, active_animation: &mut Animation) {
+    egui::CollapsingHeader::new("Retargeting").show(ui, |ui| {
+        ui.checkbox(&mut active_animation.retarget_rest_pose, "Correct Rest Pose");
+
+        let mut removed_mapping = None;
+        for (mapping_index, bone_mapping) in active_animation.bone_mappings.iter_mut().enumerate() {
+            ui.push_id(mapping_index, |ui| {
+                ui.horizontal(|ui| {
+                    if ui.add(icon(IconType::Remove)).clicked() {
+                        removed_mapping = Some(mapping_index);
+                    }
+
+                    egui::ComboBox::from_label("Drives").selected_text(&bone_mapping.source_bone).show_ui(ui, |ui| {
+                        for source_bone_name in file_data.skeleton.keys() {
+                            ui.selectable_value(&mut bone_mapping.source_bone, source_bone_name.clone(), source_bone_name);
+                        }
+                    });
+
+                    let target_label = ui.label("Model Bone: ");
+                    ui.text_edit_singleline(&mut bone_mapping.target_bone).labelled_by(target_label.id);
+                });
+            });
+        }
+
+        if let Some(removed_mapping_index) = removed_mapping {
+            active_animation.bone_mappings.remove(removed_mapping_index);
+        }
+
+        if ui.button("Add Bone Mapping").clicked() {
+            active_animation.bone_mappings.push(Default::default());
+        }
+    });
+}
+
+fn render_motion_options(ui: &mut egui::Ui, active_animation: &mut Animation) {
+    egui::CollapsingHeader::new("Motion Extraction").show(ui, |ui| {
+        ui.horizontal(|ui| {
+            ui.label("Location: ");
+            ui.checkbox(&mut active_animation.motion_x, "X");
+            ui.checkbox(&mut active_animation.motion_y, "Y");
+            ui.checkbox(&mut active_animation.motion_z, "Z");
+        });
+
+        ui.horizontal(|ui| {
+            ui.label("Rotation: ");
+            ui.checkbox(&mut active_animation.motion_x_rotation, "X");
+            ui.checkbox(&mut active_animation.motion_y_rotation, "Y");
+            ui.checkbox(&mut active_animation.motion_z_rotation, "Z");
+        });
+
+        ui.horizontal(|ui| {
+            ui.checkbox(&mut active_animation.motion_piecewise, "");
+            ui.label("Piecewise: ");
+            if active_animation.motion_piecewise {
+                ui.label("Segment Frames:");
+                ui.add(egui::DragValue::new(&mut active_animation.motion_segment_frames).range(1..=usize::MAX));
+            } else {
+                ui.label("Linear");
+            }
+        });
+    });
 }
@@ -1,6 +1,15 @@
 use crate::{
-    input::BoneProperty,
-    interface::{fix_naming_conflicts, lists::ListPanel},
+    error,
+    import::FileManager,
+    info,
+    input::{BoneController, BoneControllerAxis, BoneProperty, BoneRename, BoneRenameKind, ProceduralBone, SourceInput},
+    interface::{
+        fix_naming_conflicts,
+        icons::{IconType, icon},
+        lists::ListPanel,
+    },
+    process::{SkeletonReport, create_skeleton_report},
+    utilities::vrd::parse_vrd,
 };
 
 use super::TabViewer;
@@ -18,6 +27,11 @@ impl<'a> TabViewer<'a> {
         egui::CentralPanel::default().show(ui, |ui| {
             ui.heading("Bone Properties");
             ui.separator();
+            render_bone_renames(ui, &mut self.input_data.bone_renames);
+            render_skeleton_report(ui, self.input_data, self.loaded_files);
+            render_procedural_bones(ui, &mut self.input_data.procedural_bones);
+            render_bone_controllers(ui, &mut self.input_data.bone_controllers);
+            ui.separator();
 
             if let Some(active_bone_property_index) = selected_bone_property {
                 self.render_properties(ui, active_bone_property_index);
@@ -42,10 +56,226 @@ impl<'a> TabViewer<'a> {
         let active_bone_property = &mut self.input_data.bone_properties[active_bone_property_index];
         render_hierarchy_options(ui, active_bone_property);
         render_transform_options(ui, active_bone_property);
+        render_collapse_options(ui, active_bone_property);
+        render_jiggle_options(ui, active_bone_property);
         render_ik_chain_options(ui, active_bone_property);
     }
 }
 
+fn render_bone_renames(ui: &mut egui::Ui, bone_renames: &mut Vec<BoneRename>) {
+    egui::CollapsingHeader::new("Bone Renames").show(ui, |ui| {
+        let mut removed_rename = None;
+        for (rename_index, bone_rename) in bone_renames.iter_mut().enumerate() {
+            ui.push_id(rename_index, |ui| {
+                ui.horizontal(|ui| {
+                    if ui.add(icon(IconType::Remove)).clicked() {
+                        removed_rename = Some(rename_index);
+                    }
+
+                    egui::ComboBox::from_id_salt("Bone Rename Kind")
+                        .selected_text(format!("{:?}", bone_rename.kind))
+                        .show_ui(ui, |ui| {
+                            ui.selectable_value(&mut bone_rename.kind, BoneRenameKind::Exact, "Exact");
+                            ui.selectable_value(&mut bone_rename.kind, BoneRenameKind::Prefix, "Prefix");
+                            ui.selectable_value(&mut bone_rename.kind, BoneRenameKind::Regex, "Regex");
+                        });
+
+                    let pattern_label = ui.label("Match: ");
+                    ui.text_edit_singleline(&mut bone_rename.pattern).labelled_by(pattern_label.id);
+                    let replacement_label = ui.label("Replace: ");
+                    ui.text_edit_singleline(&mut bone_rename.replacement).labelled_by(replacement_label.id);
+                });
+            });
+        }
+
+        if let Some(removed_rename_index) = removed_rename {
+            bone_renames.remove(removed_rename_index);
+        }
+
+        if ui.button("Add Bone Rename").clicked() {
+            bone_renames.push(Default::default());
+        }
+    });
+}
+
+fn render_procedural_bones(ui: &mut egui::Ui, procedural_bones: &mut Vec<ProceduralBone>) {
+    egui::CollapsingHeader::new("Procedural Bones").show(ui, |ui| {
+        ui.horizontal(|ui| {
+            if ui.button("Import VRD…").clicked()
+                && let Some(path) = rfd::FileDialog::new().set_title("Select VRD File").add_filter("VRD", &["vrd"]).pick_file()
+            {
+                match std::fs::read_to_string(&path)
+                    .map_err(|error| error.to_string())
+                    .and_then(|text| parse_vrd(&text).map_err(|error| error.to_string()))
+                {
+                    Ok(imported_bones) => {
+                        info!("Imported {} procedural bones.", imported_bones.len());
+                        *procedural_bones = imported_bones;
+                    }
+                    Err(error) => error!("Failed To Import VRD: {error}!"),
+                }
+            }
+
+            if ui.button("Clear").clicked() {
+                procedural_bones.clear();
+            }
+        });
+
+        if procedural_bones.is_empty() {
+            ui.label("No Procedural Bones");
+        }
+
+        let mut removed_bone = None;
+        for (procedural_index, procedural_bone) in procedural_bones.iter().enumerate() {
+            ui.push_id(procedural_index, |ui| {
+                ui.horizontal(|ui| {
+                    if ui.add(icon(IconType::Remove)).clicked() {
+                        removed_bone = Some(procedural_index);
+                    }
+
+                    match procedural_bone {
+                        ProceduralBone::Quaternion(quaternion_bone) => ui.label(format!(
+                            "Helper \"{}\" Controlled By \"{}\" With {} Triggers",
+                            quaternion_bone.bone,
+                            quaternion_bone.control,
+                            quaternion_bone.triggers.len()
+                        )),
+                        ProceduralBone::AimAt(aim_at_bone) => ui.label(format!("Aim \"{}\" At \"{}\"", aim_at_bone.bone, aim_at_bone.aim)),
+                    };
+                });
+            });
+        }
+
+        if let Some(removed_bone_index) = removed_bone {
+            procedural_bones.remove(removed_bone_index);
+        }
+    });
+}
+
+fn render_bone_controllers(ui: &mut egui::Ui, bone_controllers: &mut Vec<BoneController>) {
+    egui::CollapsingHeader::new("Bone Controllers").show(ui, |ui| {
+        let mut removed_controller = None;
+        for (controller_index, bone_controller) in bone_controllers.iter_mut().enumerate() {
+            ui.push_id(controller_index, |ui| {
+                ui.horizontal(|ui| {
+                    if ui.add(icon(IconType::Remove)).clicked() {
+                        removed_controller = Some(controller_index);
+                    }
+
+                    let bone_label = ui.label("Bone: ");
+                    ui.text_edit_singleline(&mut bone_controller.bone).labelled_by(bone_label.id);
+
+                    egui::ComboBox::from_id_salt("Bone Controller Axis")
+                        .selected_text(format!("{:?}", bone_controller.axis))
+                        .show_ui(ui, |ui| {
+                            ui.selectable_value(&mut bone_controller.axis, BoneControllerAxis::X, "X");
+                            ui.selectable_value(&mut bone_controller.axis, BoneControllerAxis::Y, "Y");
+                            ui.selectable_value(&mut bone_controller.axis, BoneControllerAxis::Z, "Z");
+                            ui.selectable_value(&mut bone_controller.axis, BoneControllerAxis::XR, "XR");
+                            ui.selectable_value(&mut bone_controller.axis, BoneControllerAxis::YR, "YR");
+                            ui.selectable_value(&mut bone_controller.axis, BoneControllerAxis::ZR, "ZR");
+                        });
+                });
+
+                ui.horizontal(|ui| {
+                    ui.label("Range:");
+                    ui.add(egui::DragValue::new(&mut bone_controller.start).speed(0.1));
+                    ui.add(egui::DragValue::new(&mut bone_controller.end).speed(0.1));
+                    ui.label("Rest:");
+                    ui.add(egui::DragValue::new(&mut bone_controller.rest).speed(0.1));
+                    ui.checkbox(&mut bone_controller.mouth, "Mouth");
+                    ui.add_enabled_ui(!bone_controller.mouth, |ui| {
+                        ui.label("Index:");
+                        ui.add(egui::DragValue::new(&mut bone_controller.index).range(0..=3));
+                    });
+                });
+            });
+        }
+
+        if let Some(removed_controller_index) = removed_controller {
+            bone_controllers.remove(removed_controller_index);
+        }
+
+        if ui.button("Add Bone Controller").clicked() {
+            bone_controllers.push(Default::default());
+        }
+    });
+}
+
+fn render_skeleton_report(ui: &mut egui::Ui, input_data: &mut SourceInput, loaded_files: &FileManager) {
+    egui::CollapsingHeader::new("Skeleton Report").show(ui, |ui| {
+        ui.horizontal(|ui| {
+            ui.label("Location Tolerance:");
+            ui.add(
+                egui::DragValue::new(&mut input_data.skeleton_tolerances.location)
+                    .range(0.0..=f64::MAX)
+                    .speed(0.001),
+            );
+            ui.label("Rotation Tolerance:");
+            ui.add(
+                egui::DragValue::new(&mut input_data.skeleton_tolerances.rotation)
+                    .range(0.0..=180.0)
+                    .speed(0.01)
+                    .suffix("°"),
+            );
+        });
+
+        let report_id = ui.id().with("Skeleton Report");
+        let mut skeleton_report = ui.data_mut(|data| data.get_temp::<Option<SkeletonReport>>(report_id)).flatten();
+
+        if ui.button("Check Skeletons").clicked() {
+            match create_skeleton_report(input_data, loaded_files) {
+                Ok(report) => skeleton_report = Some(report),
+                Err(error) => error!("Failed To Check Skeletons: {error}!"),
+            }
+        }
+
+        let show_all_id = ui.id().with("Skeleton Report Show All");
+        let mut show_all = ui.data_mut(|data| data.get_temp::<bool>(show_all_id)).unwrap_or_default();
+        ui.checkbox(&mut show_all, "Show All Bones");
+        ui.data_mut(|data| data.insert_temp(show_all_id, show_all));
+
+        if let Some(report) = &skeleton_report {
+            let reported_bones = if show_all {
+                report.bones.iter().collect::<Vec<_>>()
+            } else {
+                report.mismatched_bones().collect::<Vec<_>>()
+            };
+
+            if report.mismatched_bones().next().is_none() {
+                ui.label(format!("All {} Bones Match", report.bones.len()));
+            }
+
+            for (bone_name, bone_sources) in reported_bones {
+                egui::CollapsingHeader::new(bone_name).show(ui, |ui| {
+                    egui::Grid::new(bone_name).striped(true).show(ui, |ui| {
+                        ui.label("File");
+                        ui.label("Parent");
+                        ui.label("Location Delta");
+                        ui.label("Rotation Delta");
+                        ui.end_row();
+
+                        for bone_source in bone_sources {
+                            let file_name = bone_source.file.file_name().map(|name| name.to_string_lossy()).unwrap_or_default();
+                            if bone_source.mismatched {
+                                ui.colored_label(ui.visuals().warn_fg_color, file_name);
+                            } else {
+                                ui.label(file_name);
+                            }
+                            ui.label(bone_source.parent.as_deref().unwrap_or("None"));
+                            ui.label(format!("{:.4}", bone_source.location_delta));
+                            ui.label(format!("{:.4}°", bone_source.rotation_delta));
+                            ui.end_row();
+                        }
+                    });
+                });
+            }
+        }
+
+        ui.data_mut(|data| data.insert_temp(report_id, skeleton_report));
+    });
+}
+
 fn render_hierarchy_options(ui: &mut egui::Ui, active_bone_property: &mut BoneProperty) {
     ui.horizontal(|ui| {
         ui.checkbox(&mut active_bone_property.define_parent, "");
@@ -88,6 +318,130 @@ fn render_transform_options(ui: &mut egui::Ui, active_bone_property: &mut BonePr
     });
 }
 
+fn render_collapse_options(ui: &mut egui::Ui, active_bone_property: &mut BoneProperty) {
+    ui.horizontal(|ui| {
+        if ui.checkbox(&mut active_bone_property.keep_bone, "Keep Bone").changed() && active_bone_property.keep_bone {
+            active_bone_property.force_collapse = false;
+        }
+
+        if ui.checkbox(&mut active_bone_property.force_collapse, "Force Collapse").changed() && active_bone_property.force_collapse {
+            active_bone_property.keep_bone = false;
+        }
+    });
+}
+
+fn render_jiggle_options(ui: &mut egui::Ui, active_bone_property: &mut BoneProperty) {
+    ui.horizontal(|ui| {
+        ui.checkbox(&mut active_bone_property.jiggle_bone, "");
+        ui.label("Jiggle Bone: ");
+        if !active_bone_property.jiggle_bone {
+            ui.label("Not A Jiggle Bone");
+        }
+    });
+
+    let jiggle = &mut active_bone_property.jiggle;
+    egui::CollapsingHeader::new("Jiggle Bone Data")
+        .enabled(active_bone_property.jiggle_bone)
+        .open(if active_bone_property.jiggle_bone { None } else { Some(false) })
+        .show(ui, |ui| {
+            ui.horizontal(|ui| {
+                ui.radio_value(&mut jiggle.rigid, false, "Flexible");
+                ui.radio_value(&mut jiggle.rigid, true, "Rigid");
+            });
+
+            ui.horizontal(|ui| {
+                ui.label("Length:");
+                ui.add(egui::DragValue::new(&mut jiggle.length).range(0.0..=f64::MAX).speed(0.1));
+                ui.label("Tip Mass:");
+                ui.add(egui::DragValue::new(&mut jiggle.tip_mass).range(0.0..=f64::MAX).speed(0.1));
+            });
+
+            if !jiggle.rigid {
+                ui.horizontal(|ui| {
+                    ui.label("Yaw Stiffness:");
+                    ui.add(egui::DragValue::new(&mut jiggle.yaw_stiffness).range(0.0..=1000.0));
+                    ui.label("Damping:");
+                    ui.add(egui::DragValue::new(&mut jiggle.yaw_damping).range(0.0..=10.0).speed(0.01));
+                });
+
+                ui.horizontal(|ui| {
+                    ui.label("Pitch Stiffness:");
+                    ui.add(egui::DragValue::new(&mut jiggle.pitch_stiffness).range(0.0..=1000.0));
+                    ui.label("Damping:");
+                    ui.add(egui::DragValue::new(&mut jiggle.pitch_damping).range(0.0..=10.0).speed(0.01));
+                });
+
+                ui.horizontal(|ui| {
+                    ui.checkbox(&mut jiggle.allow_length_flex, "Length Flex");
+                    if jiggle.allow_length_flex {
+                        ui.label("Along Stiffness:");
+                        ui.add(egui::DragValue::new(&mut jiggle.along_stiffness).range(0.0..=1000.0));
+                        ui.label("Damping:");
+                        ui.add(egui::DragValue::new(&mut jiggle.along_damping).range(0.0..=10.0).speed(0.01));
+                    }
+                });
+            }
+
+            ui.horizontal(|ui| {
+                ui.checkbox(&mut jiggle.define_angle_limit, "Angle Limit");
+                if jiggle.define_angle_limit {
+                    ui.add(egui::DragValue::new(&mut jiggle.angle_limit).range(0.0..=180.0).suffix("°"));
+                }
+            });
+
+            ui.horizontal(|ui| {
+                ui.checkbox(&mut jiggle.define_yaw_constraint, "Yaw Constraint");
+                if jiggle.define_yaw_constraint {
+                    ui.label("Minimum:");
+                    ui.add(egui::DragValue::new(&mut jiggle.minimum_yaw).range(-180.0..=0.0).suffix("°"));
+                    ui.label("Maximum:");
+                    ui.add(egui::DragValue::new(&mut jiggle.maximum_yaw).range(0.0..=180.0).suffix("°"));
+                    ui.label("Friction:");
+                    ui.add(egui::DragValue::new(&mut jiggle.yaw_friction).range(0.0..=f64::MAX).speed(0.1));
+                    ui.label("Bounce:");
+                    ui.add(egui::DragValue::new(&mut jiggle.yaw_bounce).range(0.0..=f64::MAX).speed(0.1));
+                }
+            });
+
+            ui.horizontal(|ui| {
+                ui.checkbox(&mut jiggle.define_pitch_constraint, "Pitch Constraint");
+                if jiggle.define_pitch_constraint {
+                    ui.label("Minimum:");
+                    ui.add(egui::DragValue::new(&mut jiggle.minimum_pitch).range(-180.0..=0.0).suffix("°"));
+                    ui.label("Maximum:");
+                    ui.add(egui::DragValue::new(&mut jiggle.maximum_pitch).range(0.0..=180.0).suffix("°"));
+                    ui.label("Friction:");
+                    ui.add(egui::DragValue::new(&mut jiggle.pitch_friction).range(0.0..=f64::MAX).speed(0.1));
+                    ui.label("Bounce:");
+                    ui.add(egui::DragValue::new(&mut jiggle.pitch_bounce).range(0.0..=f64::MAX).speed(0.1));
+                }
+            });
+
+            ui.checkbox(&mut jiggle.base_spring, "Base Spring");
+            if jiggle.base_spring {
+                ui.horizontal(|ui| {
+                    ui.label("Base Mass:");
+                    ui.add(egui::DragValue::new(&mut jiggle.base_mass).range(0.0..=f64::MAX).speed(0.1));
+                    ui.label("Stiffness:");
+                    ui.add(egui::DragValue::new(&mut jiggle.base_stiffness).range(0.0..=1000.0));
+                    ui.label("Damping:");
+                    ui.add(egui::DragValue::new(&mut jiggle.base_damping).range(0.0..=10.0).speed(0.01));
+                });
+
+                for (axis, axis_name) in ["Left", "Up", "Forward"].into_iter().enumerate() {
+                    ui.horizontal(|ui| {
+                        ui.label(format!("{axis_name} Minimum:"));
+                        ui.add(egui::DragValue::new(&mut jiggle.base_minimum[axis]).speed(0.1));
+                        ui.label("Maximum:");
+                        ui.add(egui::DragValue::new(&mut jiggle.base_maximum[axis]).speed(0.1));
+                        ui.label("Friction:");
+                        ui.add(egui::DragValue::new(&mut jiggle.base_friction[axis]).range(0.0..=f64::MAX).speed(0.1));
+                    });
+                }
+            }
+        });
+}
+
 fn render_ik_chain_options(ui: &mut egui::Ui, active_bone_property: &mut BoneProperty) {
     ui.horizontal(|ui| {
         ui.checkbox(&mut active_bone_property.ik_chain, "");
@@ -121,18 +475,20 @@ fn render_ik_chain_options(ui: &mut egui::Ui, active_bone_property: &mut BonePro
                 ui.label("Auto Play Lock: ");
 
                 if active_bone_property.ik_chain_auto_play {
-                    ui.label("Position Lock: ");
+                    ui.label("Position Weight: ");
                     ui.add(
-                        egui::DragValue::new(&mut active_bone_property.ik_chain_position_lock)
+                        egui::DragValue::new(&mut active_bone_property.ik_chain_position_weight)
                             .range(-1.0..=1.0)
                             .speed(0.01),
-                    );
-                    ui.label("Rotation Lock: ");
+                    )
+                    .on_hover_text("1 Keeps The Foot At Its Locked Position");
+                    ui.label("Rotation Weight: ");
                     ui.add(
-                        egui::DragValue::new(&mut active_bone_property.ik_chain_rotation_lock)
+                        egui::DragValue::new(&mut active_bone_property.ik_chain_rotation_weight)
                             .range(-1.0..=1.0)
                             .speed(0.01),
-                    );
+                    )
+                    .on_hover_text("1 Keeps The Foot At Its Locked Rotation");
                 } else {
                     ui.label("No Lock");
                 }
@@ -0,0 +1,174 @@
+use crate::{
+    error, info,
+    interface::icons::{IconType, icon},
+    utilities::keyvalues::{KeyValue, KeyValueData, parse_key_values},
+};
+
+use super::TabViewer;
+use eframe::egui;
+
+/// The common bases from the game prop data file.
+const PROP_DATA_BASES: [&str; 27] = [
+    "Cardboard.Small",
+    "Cardboard.Medium",
+    "Cardboard.Large",
+    "Cloth.Small",
+    "Cloth.Medium",
+    "Cloth.Large",
+    "Wooden.Tiny",
+    "Wooden.Small",
+    "Wooden.Medium",
+    "Wooden.Large",
+    "Wooden.Huge",
+    "Stone.Small",
+    "Stone.Medium",
+    "Stone.Large",
+    "Stone.Huge",
+    "Glass.Small",
+    "Glass.Window",
+    "Metal.Small",
+    "Metal.Medium",
+    "Metal.Large",
+    "Plastic.Small",
+    "Plastic.Medium",
+    "Plastic.Large",
+    "Pottery.Small",
+    "Pottery.Medium",
+    "Pottery.Large",
+    "Flesh.Small",
+];
+
+impl<'a> TabViewer<'a> {
+    pub fn render_keyvalues(&mut self, ui: &mut egui::Ui) {
+        egui::ScrollArea::vertical().auto_shrink([false; 2]).show(ui, |ui| {
+            ui.heading("Key Values");
+            ui.separator();
+            render_presets(ui, &mut self.input_data.keyvalues);
+            render_import(ui, &mut self.input_data.keyvalues);
+            ui.separator();
+
+            if self.input_data.keyvalues.is_empty() {
+                ui.label("No Key Values");
+            }
+
+            render_block(ui, &mut self.input_data.keyvalues);
+        });
+    }
+}
+
+fn render_presets(ui: &mut egui::Ui, keyvalues: &mut Vec<KeyValue>) {
+    ui.horizontal(|ui| {
+        let mut selected_base = None;
+        egui::ComboBox::from_label("Prop Data Base").selected_text("Select Preset…").show_ui(ui, |ui| {
+            for base in PROP_DATA_BASES {
+                if ui.selectable_label(false, base).clicked() {
+                    selected_base = Some(base);
+                }
+            }
+        });
+
+        let Some(base) = selected_base else {
+            return;
+        };
+
+        let base_key = KeyValue {
+            key: String::from("base"),
+            value: KeyValueData::Value(String::from(base)),
+        };
+
+        match keyvalues.iter_mut().find(|entry| entry.key.eq_ignore_ascii_case("prop_data")) {
+            Some(KeyValue {
+                value: KeyValueData::Block(prop_data),
+                ..
+            }) => match prop_data.iter_mut().find(|entry| entry.key.eq_ignore_ascii_case("base")) {
+                Some(existing_base) => *existing_base = base_key,
+                None => prop_data.insert(0, base_key),
+            },
+            Some(prop_data) => prop_data.value = KeyValueData::Block(vec![base_key]),
+            None => keyvalues.push(KeyValue {
+                key: String::from("prop_data"),
+                value: KeyValueData::Block(vec![base_key]),
+            }),
+        }
+    });
+}
+
+fn render_import(ui: &mut egui::Ui, keyvalues: &mut Vec<KeyValue>) {
+    egui::CollapsingHeader::new("Import Text").show(ui, |ui| {
+        let text_id = ui.id().with("Key Values Import Text");
+        let mut import_text = ui.data_mut(|data| data.get_temp::<String>(text_id)).unwrap_or_default();
+        ui.add(egui::TextEdit::multiline(&mut import_text).code_editor().desired_width(f32::INFINITY));
+
+        if ui.button("Import").clicked() {
+            match parse_key_values(&import_text) {
+                Ok(mut imported_keys) => {
+                    // Text copied from a compiled model is wrapped in the model key values block.
+                    if let [
+                        KeyValue {
+                            key,
+                            value: KeyValueData::Block(block),
+                        },
+                    ] = imported_keys.as_mut_slice()
+                        && key.eq_ignore_ascii_case("mdlkeyvalue")
+                    {
+                        imported_keys = std::mem::take(block);
+                    }
+
+                    info!("Imported {} key values.", imported_keys.len());
+                    *keyvalues = imported_keys;
+                    import_text.clear();
+                }
+                Err(error) => error!("Failed To Import Key Values: {error}!"),
+            }
+        }
+
+        ui.data_mut(|data| data.insert_temp(text_id, import_text));
+    });
+}
+
+fn render_block(ui: &mut egui::Ui, block: &mut Vec<KeyValue>) {
+    let mut removed_key = None;
+    for (key_index, entry) in block.iter_mut().enumerate() {
+        ui.push_id(key_index, |ui| {
+            ui.horizontal(|ui| {
+                if ui.add(icon(IconType::Remove)).clicked() {
+                    removed_key = Some(key_index);
+                }
+
+                let key_label = ui.label("Key: ");
+                ui.text_edit_singleline(&mut entry.key).labelled_by(key_label.id);
+
+                if let KeyValueData::Value(value) = &mut entry.value {
+                    let value_label = ui.label("Value: ");
+                    ui.text_edit_singleline(value).labelled_by(value_label.id);
+                }
+            });
+
+            if let KeyValueData::Block(child_block) = &mut entry.value {
+                ui.indent("Key Value Block", |ui| {
+                    render_block(ui, child_block);
+                });
+            }
+        });
+    }
+
+    if let Some(removed_key_index) = removed_key {
+        block.remove(removed_key_index);
+    }
+
+    ui.horizontal(|ui| {
+        if ui.button("Add Value").clicked() {
+            block.push(KeyValue {
+                key: String::from("New Key"),
+                value: KeyValueData::Value(String::new()),
+            });
+        }
+
+        if ui.button("Add Block").clicked() {
+            block.push(KeyValue {
+                key: String::from("New Block"),
+                value: KeyValueData::Block(Vec::new()),
+            });
+        }
+    });
+}
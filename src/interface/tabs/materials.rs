@@ -0,0 +1,105 @@
+use crate::{
+    input::MaterialRename,
+    interface::icons::{IconType, icon},
+};
+
+use super::TabViewer;
+use eframe::egui;
+
+impl<'a> TabViewer<'a> {
+    pub fn render_materials(&mut self, ui: &mut egui::Ui) {
+        egui::ScrollArea::vertical().auto_shrink([false; 2]).show(ui, |ui| {
+            ui.heading("Content Directory");
+            ui.separator();
+            self.render_content_directory(ui);
+
+            ui.heading("Material Paths");
+            ui.separator();
+            render_material_paths(ui, &mut self.input_data.material_paths);
+
+            ui.heading("Material Renames");
+            ui.separator();
+            render_material_renames(ui, &mut self.input_data.material_renames);
+        });
+    }
+
+    fn render_content_directory(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            let directory_label = ui.label("Game Content Directory: ");
+            let mut path_text = self
+                .input_data
+                .content_directory
+                .as_ref()
+                .map(|p| p.display().to_string())
+                .unwrap_or_else(|| String::from("Select a directory..."));
+
+            if ui.text_edit_singleline(&mut path_text).labelled_by(directory_label.id).clicked()
+                && let Some(path) = rfd::FileDialog::new().set_title("Select Game Content Directory").pick_folder()
+            {
+                self.input_data.content_directory = Some(path);
+            }
+
+            if self.input_data.content_directory.is_some() && ui.add(icon(IconType::X)).clicked() {
+                self.input_data.content_directory = None;
+            }
+        });
+
+        if self.input_data.content_directory.is_none() {
+            ui.label("Materials Will Not Be Validated");
+        }
+    }
+}
+
+fn render_material_paths(ui: &mut egui::Ui, material_paths: &mut Vec<String>) {
+    if material_paths.is_empty() {
+        ui.label("Materials Must Use Full Path");
+    }
+
+    let mut removed_path = None;
+    for (material_path_index, material_path) in material_paths.iter_mut().enumerate() {
+        ui.horizontal(|ui| {
+            if ui.add(icon(IconType::Remove)).clicked() {
+                removed_path = Some(material_path_index);
+            }
+
+            let path_label = ui.label("Path: ");
+            ui.text_edit_singleline(material_path).labelled_by(path_label.id);
+        });
+    }
+
+    if let Some(removed_path_index) = removed_path {
+        material_paths.remove(removed_path_index);
+    }
+
+    if ui.add(icon(IconType::Add)).clicked() {
+        material_paths.push(String::new());
+    }
+}
+
+fn render_material_renames(ui: &mut egui::Ui, material_renames: &mut Vec<MaterialRename>) {
+    if material_renames.is_empty() {
+        ui.label("No Material Renames");
+    }
+
+    let mut removed_rename = None;
+    for (material_rename_index, material_rename) in material_renames.iter_mut().enumerate() {
+        ui.horizontal(|ui| {
+            if ui.add(icon(IconType::Remove)).clicked() {
+                removed_rename = Some(material_rename_index);
+            }
+
+            let source_label = ui.label("Source Name: ");
+            ui.text_edit_singleline(&mut material_rename.source_name).labelled_by(source_label.id);
+            let model_label = ui.label("Model Name: ");
+            ui.text_edit_singleline(&mut material_rename.model_name).labelled_by(model_label.id);
+        });
+    }
+
+    if let Some(removed_rename_index) = removed_rename {
+        material_renames.remove(removed_rename_index);
+    }
+
+    if ui.add(icon(IconType::Add)).clicked() {
+        material_renames.push(MaterialRename::default());
+    }
+}
@@ -1,6 +1,6 @@
 use std::sync::{Arc, atomic::Ordering};
 
-use crate::{error, info, process, write};
+use crate::{error, info, input::ModelProperties, process, write};
 
 use super::TabViewer;
 use eframe::egui;
@@ -9,6 +9,7 @@ impl<'a> TabViewer<'a> {
     pub fn render_overview(&mut self, ui: &mut egui::Ui) {
         self.render_header(ui);
         self.render_output_input(ui);
+        render_model_properties(ui, &mut self.input_data.model_properties);
 
         if let Some(export_path) = &self.input_data.export_path {
             let name_label = ui.label("Model Name: ");
@@ -91,3 +92,90 @@ impl<'a> TabViewer<'a> {
         });
     }
 }
+
+fn render_model_properties(ui: &mut egui::Ui, model_properties: &mut ModelProperties) {
+    egui::CollapsingHeader::new("Model Properties").show(ui, |ui| {
+        ui.horizontal(|ui| {
+            let surface_label = ui.label("Surface Property: ");
+            ui.text_edit_singleline(&mut model_properties.surface_property).labelled_by(surface_label.id);
+        });
+
+        ui.horizontal(|ui| {
+            ui.label("Contents: ");
+            if ui.checkbox(&mut model_properties.contents_solid, "Solid").changed() && model_properties.contents_solid {
+                model_properties.contents_grate = false;
+            }
+            if ui.checkbox(&mut model_properties.contents_grate, "Grate").changed() && model_properties.contents_grate {
+                model_properties.contents_solid = false;
+            }
+            ui.checkbox(&mut model_properties.contents_monster, "Monster");
+            ui.checkbox(&mut model_properties.contents_ladder, "Ladder");
+        });
+
+        ui.horizontal(|ui| {
+            ui.label("Mass: ");
+            ui.add(egui::DragValue::new(&mut model_properties.mass).range(0.0..=f64::MAX).speed(0.1));
+        });
+
+        ui.horizontal(|ui| {
+            ui.checkbox(&mut model_properties.define_eye_position, "");
+            ui.label("Eye Position: ");
+            if model_properties.define_eye_position {
+                ui.label("X:");
+                ui.add(egui::DragValue::new(&mut model_properties.eye_position.x));
+                ui.label("Y:");
+                ui.add(egui::DragValue::new(&mut model_properties.eye_position.y));
+                ui.label("Z:");
+                ui.add(egui::DragValue::new(&mut model_properties.eye_position.z));
+            } else {
+                ui.label("Model Origin");
+            }
+        });
+
+        ui.horizontal(|ui| {
+            ui.checkbox(&mut model_properties.define_illumination_position, "");
+            ui.label("Illumination Position: ");
+            if model_properties.define_illumination_position {
+                ui.label("X:");
+                ui.add(egui::DragValue::new(&mut model_properties.illumination_position.x));
+                ui.label("Y:");
+                ui.add(egui::DragValue::new(&mut model_properties.illumination_position.y));
+                ui.label("Z:");
+                ui.add(egui::DragValue::new(&mut model_properties.illumination_position.z));
+            } else {
+                ui.label("Model Center");
+            }
+        });
+
+        ui.horizontal(|ui| {
+            ui.checkbox(&mut model_properties.define_constant_directional_light_dot, "");
+            ui.label("Constant Directional Light Dot: ");
+            if model_properties.define_constant_directional_light_dot {
+                ui.add(
+                    egui::DragValue::new(&mut model_properties.constant_directional_light_dot)
+                        .range(0.0..=1.0)
+                        .speed(0.01),
+                );
+            } else {
+                ui.label("Not Constant");
+            }
+        });
+
+        egui::CollapsingHeader::new("Flags").show(ui, |ui| {
+            ui.checkbox(&mut model_properties.auto_generated_hitbox, "Auto Generated Hitbox");
+            if ui.checkbox(&mut model_properties.force_opaque, "Force Opaque").changed() && model_properties.force_opaque {
+                model_properties.translucent_two_pass = false;
+            }
+            if ui.checkbox(&mut model_properties.translucent_two_pass, "Translucent Two Pass").changed() && model_properties.translucent_two_pass {
+                model_properties.force_opaque = false;
+            }
+            ui.checkbox(&mut model_properties.static_prop, "Static Prop");
+            ui.checkbox(&mut model_properties.obsolete, "Obsolete");
+            ui.checkbox(&mut model_properties.no_forced_fade, "No Forced Fade");
+            ui.checkbox(&mut model_properties.force_phoneme_cross_fade, "Force Phoneme Cross Fade");
+            ui.checkbox(&mut model_properties.ambient_boost, "Ambient Boost");
+            ui.checkbox(&mut model_properties.do_not_cast_shadows, "Do Not Cast Shadows");
+            ui.checkbox(&mut model_properties.cast_texture_shadows, "Cast Texture Shadows");
+        });
+    });
+}
@@ -1,8 +1,19 @@
-use crate::interface::{fix_naming_conflicts, lists::ListPanel};
+use crate::{
+    import::FileManager,
+    input::{Animation, AutoLayer, BlendAxis, IkRule, IkRuleKind, PoseParameter, Sequence, SequenceEvent, WeightList},
+    interface::{
+        fix_naming_conflicts,
+        icons::{IconType, icon},
+        lists::ListPanel,
+    },
+};
 
 use super::TabViewer;
 use eframe::egui;
 
+/// The maximum amount of animations on a blend axis.
+const MAX_BLEND_SIZE: usize = 32;
+
 impl<'a> TabViewer<'a> {
     pub fn render_sequences(&mut self, ui: &mut egui::Ui) {
         let mut selected_sequence = None;
@@ -15,6 +26,9 @@ impl<'a> TabViewer<'a> {
         egui::CentralPanel::default().show(ui, |ui| {
             ui.heading("Sequences");
             ui.separator();
+            render_pose_parameters(ui, &mut self.input_data.pose_parameters);
+            render_weight_lists(ui, &mut self.input_data.weight_lists);
+            ui.separator();
 
             if let Some(active_sequence_index) = selected_sequence {
                 let name_label = ui.label("Sequence Name: ");
@@ -26,6 +40,7 @@ impl<'a> TabViewer<'a> {
                     fix_naming_conflicts(&mut self.input_data.sequences, active_sequence_index);
                 }
 
+                let sequence_names = self.input_data.sequences.iter().map(|sequence| sequence.name.clone()).collect::<Vec<_>>();
                 let active_sequence = &mut self.input_data.sequences[active_sequence_index];
                 if self.input_data.animations.is_empty() {
                     ui.colored_label(egui::Color32::RED, "No Animations Created");
@@ -41,28 +56,566 @@ impl<'a> TabViewer<'a> {
                     active_sequence.animations = vec![vec![0]];
                 }
 
-                let sequence_animation = &mut active_sequence.animations[0][0];
+                render_blend_grid(ui, active_sequence, &self.input_data.animations);
+                render_blend_axes(ui, active_sequence, &self.input_data.pose_parameters);
+                render_weight_list_option(ui, active_sequence, &self.input_data.weight_lists);
+                render_activity_options(ui, active_sequence);
+                render_flag_options(ui, active_sequence);
+                render_transition_options(ui, active_sequence);
+
+                let frame_count = first_animation_frame_count(active_sequence, &self.input_data.animations, self.loaded_files);
+                render_events(ui, active_sequence, frame_count);
+                render_auto_layers(ui, active_sequence, &sequence_names, &self.input_data.pose_parameters);
+                render_ik_rules(ui, active_sequence, frame_count);
+                render_ik_locks(ui, active_sequence);
+                return;
+            }
+            ui.label("No Sequences");
+        });
+    }
+}
+
+fn render_pose_parameters(ui: &mut egui::Ui, pose_parameters: &mut Vec<PoseParameter>) {
+    egui::CollapsingHeader::new("Pose Parameters").show(ui, |ui| {
+        let mut removed_pose_parameter = None;
+        let mut renamed_pose_parameter = None;
+        for (pose_parameter_index, pose_parameter) in pose_parameters.iter_mut().enumerate() {
+            ui.push_id(pose_parameter_index, |ui| {
+                ui.horizontal(|ui| {
+                    if ui.add(icon(IconType::Remove)).clicked() {
+                        removed_pose_parameter = Some(pose_parameter_index);
+                    }
+
+                    let name_label = ui.label("Name: ");
+                    if ui.text_edit_singleline(&mut pose_parameter.name).labelled_by(name_label.id).lost_focus() {
+                        renamed_pose_parameter = Some(pose_parameter_index);
+                    }
+                });
+
+                ui.horizontal(|ui| {
+                    ui.label("Range:");
+                    ui.add(egui::DragValue::new(&mut pose_parameter.start).speed(0.1));
+                    ui.add(egui::DragValue::new(&mut pose_parameter.end).speed(0.1));
+                    ui.checkbox(&mut pose_parameter.define_loop, "Loop");
+                    ui.add_enabled(
+                        pose_parameter.define_loop,
+                        egui::DragValue::new(&mut pose_parameter.loop_range).speed(0.1).range(0.0..=f64::MAX),
+                    );
+                });
+            });
+        }
+
+        if let Some(renamed_pose_parameter_index) = renamed_pose_parameter {
+            fix_naming_conflicts(pose_parameters, renamed_pose_parameter_index);
+        }
+
+        if let Some(removed_pose_parameter_index) = removed_pose_parameter {
+            pose_parameters.remove(removed_pose_parameter_index);
+        }
+
+        if ui.button("Add Pose Parameter").clicked() {
+            pose_parameters.push(Default::default());
+            let new_pose_parameter_index = pose_parameters.len() - 1;
+            fix_naming_conflicts(pose_parameters, new_pose_parameter_index);
+        }
+    });
+}
+
+fn render_weight_lists(ui: &mut egui::Ui, weight_lists: &mut Vec<WeightList>) {
+    egui::CollapsingHeader::new("Weight Lists").show(ui, |ui| {
+        let mut removed_weight_list = None;
+        let mut renamed_weight_list = None;
+        for (weight_list_index, weight_list) in weight_lists.iter_mut().enumerate() {
+            ui.push_id(weight_list_index, |ui| {
+                ui.horizontal(|ui| {
+                    if ui.add(icon(IconType::Remove)).clicked() {
+                        removed_weight_list = Some(weight_list_index);
+                    }
+
+                    let name_label = ui.label("Name: ");
+                    if ui.text_edit_singleline(&mut weight_list.name).labelled_by(name_label.id).lost_focus() {
+                        renamed_weight_list = Some(weight_list_index);
+                    }
+                });
+
+                ui.indent("Bone Weights", |ui| {
+                    let mut removed_weight = None;
+                    for (weight_index, bone_weight) in weight_list.weights.iter_mut().enumerate() {
+                        ui.push_id(weight_index, |ui| {
+                            ui.horizontal(|ui| {
+                                if ui.add(icon(IconType::Remove)).clicked() {
+                                    removed_weight = Some(weight_index);
+                                }
+
+                                let bone_label = ui.label("Bone: ");
+                                ui.text_edit_singleline(&mut bone_weight.bone).labelled_by(bone_label.id);
+                                ui.add(egui::Slider::new(&mut bone_weight.weight, 0.0..=1.0).text("Weight"));
+                            });
+                        });
+                    }
+
+                    if let Some(removed_weight_index) = removed_weight {
+                        weight_list.weights.remove(removed_weight_index);
+                    }
+
+                    if ui.button("Add Bone Weight").clicked() {
+                        weight_list.weights.push(Default::default());
+                    }
+                });
+            });
+        }
+
+        if let Some(renamed_weight_list_index) = renamed_weight_list {
+            fix_naming_conflicts(weight_lists, renamed_weight_list_index);
+        }
+
+        if let Some(removed_weight_list_index) = removed_weight_list {
+            weight_lists.remove(removed_weight_list_index);
+        }
+
+        if ui.button("Add Weight List").clicked() {
+            weight_lists.push(Default::default());
+            let new_weight_list_index = weight_lists.len() - 1;
+            fix_naming_conflicts(weight_lists, new_weight_list_index);
+        }
+    });
+}
+
+fn render_blend_grid(ui: &mut egui::Ui, active_sequence: &mut Sequence, animations: &[Animation]) {
+    let mut column_count = active_sequence.animations[0].len();
+    let mut row_count = active_sequence.animations.len();
+    ui.horizontal(|ui| {
+        ui.label("Columns:");
+        ui.add(egui::DragValue::new(&mut column_count).range(1..=MAX_BLEND_SIZE));
+        ui.label("Rows:");
+        ui.add(egui::DragValue::new(&mut row_count).range(1..=MAX_BLEND_SIZE));
+    });
+
+    // New cells use the first animation of the sequence.
+    let first_animation = active_sequence.animations[0][0];
+    active_sequence.animations.resize_with(row_count, || vec![first_animation; column_count]);
+    for row in &mut active_sequence.animations {
+        row.resize(column_count, first_animation);
+    }
 
-                let active_animation = self
-                    .input_data
-                    .animations
-                    .iter()
-                    .position(|animation| *sequence_animation == animation.animation_identifier);
+    egui::Grid::new("Sequence Blend Grid").striped(true).show(ui, |ui| {
+        for (row_index, row) in active_sequence.animations.iter_mut().enumerate() {
+            for (column_index, sequence_animation) in row.iter_mut().enumerate() {
+                let active_animation = animations.iter().position(|animation| *sequence_animation == animation.animation_identifier);
 
                 if active_animation.is_none() {
-                    *sequence_animation = self.input_data.animations[0].animation_identifier;
+                    *sequence_animation = animations[0].animation_identifier;
                 }
 
-                egui::ComboBox::from_label("Selected Animation")
-                    .selected_text(&self.input_data.animations[active_animation.unwrap_or_default()].name)
+                egui::ComboBox::from_id_salt(("Sequence Blend Animation", row_index, column_index))
+                    .selected_text(&animations[active_animation.unwrap_or_default()].name)
                     .show_ui(ui, |ui| {
-                        for animation in &self.input_data.animations {
+                        for animation in animations {
                             ui.selectable_value(sequence_animation, animation.animation_identifier, &animation.name);
                         }
                     });
-                return;
             }
-            ui.label("No Sequences");
+            ui.end_row();
+        }
+    });
+}
+
+fn render_blend_axes(ui: &mut egui::Ui, active_sequence: &mut Sequence, pose_parameters: &[PoseParameter]) {
+    let axis_sizes = [active_sequence.animations[0].len(), active_sequence.animations.len()];
+    for (axis_index, (axis_name, blend_axis)) in ["Column Blend", "Row Blend"].into_iter().zip(&mut active_sequence.blend_axes).enumerate() {
+        ui.push_id(axis_name, |ui| {
+            render_blend_axis(ui, axis_name, blend_axis, pose_parameters, axis_sizes[axis_index] > 1);
         });
     }
 }
+
+fn render_blend_axis(ui: &mut egui::Ui, axis_name: &str, blend_axis: &mut BlendAxis, pose_parameters: &[PoseParameter], required: bool) {
+    ui.horizontal(|ui| {
+        egui::ComboBox::from_label(axis_name)
+            .selected_text(if blend_axis.parameter.is_empty() { "None" } else { &blend_axis.parameter })
+            .show_ui(ui, |ui| {
+                ui.selectable_value(&mut blend_axis.parameter, String::new(), "None");
+                for pose_parameter in pose_parameters {
+                    ui.selectable_value(&mut blend_axis.parameter, pose_parameter.name.clone(), &pose_parameter.name);
+                }
+            });
+
+        if blend_axis.parameter.is_empty() {
+            if required {
+                ui.colored_label(egui::Color32::RED, "Pose Parameter Required");
+            }
+            return;
+        }
+
+        if !pose_parameters.iter().any(|pose_parameter| pose_parameter.name == blend_axis.parameter) {
+            ui.colored_label(egui::Color32::RED, "Pose Parameter Not Found");
+        }
+
+        ui.checkbox(&mut blend_axis.define_range, "Define Range");
+        ui.add_enabled(blend_axis.define_range, egui::DragValue::new(&mut blend_axis.start).speed(0.1));
+        ui.add_enabled(blend_axis.define_range, egui::DragValue::new(&mut blend_axis.end).speed(0.1));
+    });
+}
+
+fn render_weight_list_option(ui: &mut egui::Ui, active_sequence: &mut Sequence, weight_lists: &[WeightList]) {
+    ui.horizontal(|ui| {
+        egui::ComboBox::from_label("Weight List")
+            .selected_text(if active_sequence.weight_list.is_empty() {
+                "All Bones"
+            } else {
+                &active_sequence.weight_list
+            })
+            .show_ui(ui, |ui| {
+                ui.selectable_value(&mut active_sequence.weight_list, String::new(), "All Bones");
+                for weight_list in weight_lists {
+                    ui.selectable_value(&mut active_sequence.weight_list, weight_list.name.clone(), &weight_list.name);
+                }
+            });
+
+        if !active_sequence.weight_list.is_empty() && !weight_lists.iter().any(|weight_list| weight_list.name == active_sequence.weight_list) {
+            ui.colored_label(egui::Color32::RED, "Weight List Not Found");
+        }
+    });
+}
+
+fn render_activity_options(ui: &mut egui::Ui, active_sequence: &mut Sequence) {
+    ui.horizontal(|ui| {
+        let activity_label = ui.label("Activity: ");
+        ui.text_edit_singleline(&mut active_sequence.activity).labelled_by(activity_label.id);
+        ui.add_enabled_ui(!active_sequence.activity.is_empty(), |ui| {
+            ui.label("Weight:");
+            ui.add(egui::DragValue::new(&mut active_sequence.activity_weight).range(0..=i32::MAX));
+        });
+    });
+}
+
+fn render_flag_options(ui: &mut egui::Ui, active_sequence: &mut Sequence) {
+    ui.horizontal(|ui| {
+        ui.checkbox(&mut active_sequence.looping, "Looping");
+        ui.checkbox(&mut active_sequence.snap, "Snap");
+        ui.checkbox(&mut active_sequence.delta, "Delta");
+        ui.checkbox(&mut active_sequence.auto_play, "Auto Play");
+        ui.checkbox(&mut active_sequence.realtime, "Realtime");
+        ui.checkbox(&mut active_sequence.hidden, "Hidden");
+    });
+
+    ui.horizontal(|ui| {
+        ui.label("Fade In:");
+        ui.add(
+            egui::DragValue::new(&mut active_sequence.fade_in_time)
+                .speed(0.01)
+                .range(0.0..=f64::MAX)
+                .suffix("s"),
+        );
+        ui.label("Fade Out:");
+        ui.add(
+            egui::DragValue::new(&mut active_sequence.fade_out_time)
+                .speed(0.01)
+                .range(0.0..=f64::MAX)
+                .suffix("s"),
+        );
+    });
+}
+
+fn render_transition_options(ui: &mut egui::Ui, active_sequence: &mut Sequence) {
+    ui.checkbox(&mut active_sequence.define_transition, "Node Transition");
+    if !active_sequence.define_transition {
+        return;
+    }
+
+    ui.horizontal(|ui| {
+        let entry_label = ui.label("Entry Node: ");
+        ui.text_edit_singleline(&mut active_sequence.entry_node).labelled_by(entry_label.id);
+        let exit_label = ui.label("Exit Node: ");
+        ui.text_edit_singleline(&mut active_sequence.exit_node).labelled_by(exit_label.id);
+        ui.checkbox(&mut active_sequence.reverse_transition, "Reverse");
+    });
+}
+
+/// Returns the frame count of the first animation of the sequence if the source file is loaded.
+fn first_animation_frame_count(active_sequence: &Sequence, animations: &[Animation], loaded_files: &FileManager) -> Option<usize> {
+    let first_animation = animations
+        .iter()
+        .find(|animation| animation.animation_identifier == active_sequence.animations[0][0])?;
+
+    if first_animation.define_frame_range {
+        return Some(first_animation.end_frame.saturating_sub(first_animation.start_frame) + 1);
+    }
+
+    let file_data = loaded_files.get_file_data(first_animation.source_file_path.as_ref()?)?;
+    let source_animation = file_data.animations.get_index(first_animation.source_animation)?.1;
+    Some(source_animation.frame_count.get())
+}
+
+/// Returns the cycle of the event. None if the event is placed by frame and the frame count is unknown.
+fn event_cycle(event: &SequenceEvent, frame_count: Option<usize>) -> Option<f64> {
+    if event.use_cycle {
+        return Some(event.cycle);
+    }
+
+    frame_count.map(|frame_count| event.frame as f64 / (frame_count - 1).max(1) as f64)
+}
+
+fn render_events(ui: &mut egui::Ui, active_sequence: &mut Sequence, frame_count: Option<usize>) {
+    egui::CollapsingHeader::new("Events").show(ui, |ui| {
+        render_event_timeline(ui, active_sequence, frame_count);
+
+        let mut removed_event = None;
+        for (event_index, event) in active_sequence.events.iter_mut().enumerate() {
+            ui.push_id(event_index, |ui| {
+                ui.horizontal(|ui| {
+                    if ui.add(icon(IconType::Remove)).clicked() {
+                        removed_event = Some(event_index);
+                    }
+
+                    ui.checkbox(&mut event.use_cycle, "Cycle");
+                    if event.use_cycle {
+                        ui.add(egui::DragValue::new(&mut event.cycle).speed(0.01).range(0.0..=1.0));
+                    } else {
+                        let last_frame = frame_count.map_or(usize::MAX, |frame_count| frame_count - 1);
+                        ui.add(egui::DragValue::new(&mut event.frame).range(0..=last_frame).prefix("Frame "));
+                    }
+
+                    let event_label = ui.label("Event: ");
+                    ui.text_edit_singleline(&mut event.event).labelled_by(event_label.id);
+                    let options_label = ui.label("Options: ");
+                    ui.add(egui::TextEdit::singleline(&mut event.options).char_limit(63))
+                        .labelled_by(options_label.id);
+                });
+            });
+        }
+
+        if let Some(removed_event_index) = removed_event {
+            active_sequence.events.remove(removed_event_index);
+        }
+
+        if ui.button("Add Event").clicked() {
+            active_sequence.events.push(Default::default());
+        }
+    });
+}
+
+/// Shows the events on a line from the start to the end of the sequence, clicking the line adds an event.
+fn render_event_timeline(ui: &mut egui::Ui, active_sequence: &mut Sequence, frame_count: Option<usize>) {
+    let (timeline_rect, timeline_response) = ui.allocate_exact_size(egui::vec2(ui.available_width(), 32.0), egui::Sense::click());
+    let painter = ui.painter_at(timeline_rect);
+    let visuals = ui.visuals();
+
+    painter.rect_filled(timeline_rect, 2.0, visuals.extreme_bg_color);
+    painter.hline(timeline_rect.x_range(), timeline_rect.center().y, visuals.widgets.noninteractive.fg_stroke);
+
+    for event in &active_sequence.events {
+        let Some(cycle) = event_cycle(event, frame_count) else {
+            continue;
+        };
+
+        let marker_x = timeline_rect.left() + timeline_rect.width() * cycle.clamp(0.0, 1.0) as f32;
+        painter.vline(marker_x, timeline_rect.y_range(), visuals.selection.stroke);
+        painter.text(
+            egui::pos2(marker_x + 2.0, timeline_rect.top()),
+            egui::Align2::LEFT_TOP,
+            &event.event,
+            egui::FontId::proportional(10.0),
+            visuals.text_color(),
+        );
+    }
+
+    if timeline_response.clicked()
+        && let Some(click_position) = timeline_response.interact_pointer_pos()
+    {
+        let cycle = ((click_position.x - timeline_rect.left()) / timeline_rect.width()).clamp(0.0, 1.0) as f64;
+        let event = match frame_count {
+            Some(frame_count) => SequenceEvent {
+                frame: (cycle * (frame_count - 1) as f64).round() as usize,
+                ..Default::default()
+            },
+            None => SequenceEvent {
+                use_cycle: true,
+                cycle,
+                ..Default::default()
+            },
+        };
+        active_sequence.events.push(event);
+    }
+
+    timeline_response.on_hover_text("Click To Add An Event");
+}
+
+fn render_auto_layers(ui: &mut egui::Ui, active_sequence: &mut Sequence, sequence_names: &[String], pose_parameters: &[PoseParameter]) {
+    egui::CollapsingHeader::new("Auto Layers").show(ui, |ui| {
+        let mut removed_layer = None;
+        for (layer_index, auto_layer) in active_sequence.auto_layers.iter_mut().enumerate() {
+            ui.push_id(layer_index, |ui| {
+                ui.horizontal(|ui| {
+                    if ui.add(icon(IconType::Remove)).clicked() {
+                        removed_layer = Some(layer_index);
+                    }
+
+                    egui::ComboBox::from_label("Layer Sequence")
+                        .selected_text(&auto_layer.sequence)
+                        .show_ui(ui, |ui| {
+                            for sequence_name in sequence_names.iter().filter(|sequence_name| **sequence_name != active_sequence.name) {
+                                ui.selectable_value(&mut auto_layer.sequence, sequence_name.clone(), sequence_name);
+                            }
+                        });
+
+                    ui.checkbox(&mut auto_layer.blend, "Blend");
+                });
+
+                if auto_layer.blend {
+                    render_auto_layer_blend(ui, auto_layer, pose_parameters);
+                }
+            });
+        }
+
+        if let Some(removed_layer_index) = removed_layer {
+            active_sequence.auto_layers.remove(removed_layer_index);
+        }
+
+        if ui.button("Add Auto Layer").clicked() {
+            active_sequence.auto_layers.push(Default::default());
+        }
+    });
+}
+
+fn render_auto_layer_blend(ui: &mut egui::Ui, auto_layer: &mut AutoLayer, pose_parameters: &[PoseParameter]) {
+    ui.indent("Auto Layer Blend", |ui| {
+        ui.horizontal(|ui| {
+            ui.label("Start:");
+            ui.add(egui::DragValue::new(&mut auto_layer.start).speed(0.1));
+            ui.label("Peak:");
+            ui.add(egui::DragValue::new(&mut auto_layer.peak).speed(0.1));
+            ui.label("Tail:");
+            ui.add(egui::DragValue::new(&mut auto_layer.tail).speed(0.1));
+            ui.label("End:");
+            ui.add(egui::DragValue::new(&mut auto_layer.end).speed(0.1));
+        });
+
+        ui.horizontal(|ui| {
+            ui.checkbox(&mut auto_layer.spline, "Spline");
+            ui.checkbox(&mut auto_layer.cross_fade, "Cross Fade");
+            ui.checkbox(&mut auto_layer.no_blend, "No Blend");
+            ui.checkbox(&mut auto_layer.local, "Local");
+        });
+
+        ui.horizontal(|ui| {
+            ui.checkbox(&mut auto_layer.define_pose_parameter, "Pose Parameter");
+            ui.add_enabled_ui(auto_layer.define_pose_parameter, |ui| {
+                egui::ComboBox::from_id_salt("Auto Layer Pose Parameter")
+                    .selected_text(&auto_layer.pose_parameter)
+                    .show_ui(ui, |ui| {
+                        for pose_parameter in pose_parameters {
+                            ui.selectable_value(&mut auto_layer.pose_parameter, pose_parameter.name.clone(), &pose_parameter.name);
+                        }
+                    });
+            });
+        });
+    });
+}
+
+fn render_ik_rules(ui: &mut egui::Ui, active_sequence: &mut Sequence, frame_count: Option<usize>) {
+    egui::CollapsingHeader::new("IK Rules").show(ui, |ui| {
+        let last_frame = frame_count.map_or(usize::MAX, |frame_count| frame_count - 1);
+
+        let mut removed_rule = None;
+        for (rule_index, ik_rule) in active_sequence.ik_rules.iter_mut().enumerate() {
+            ui.push_id(rule_index, |ui| {
+                ui.horizontal(|ui| {
+                    if ui.add(icon(IconType::Remove)).clicked() {
+                        removed_rule = Some(rule_index);
+                    }
+
+                    let chain_label = ui.label("Chain: ");
+                    ui.text_edit_singleline(&mut ik_rule.chain).labelled_by(chain_label.id);
+
+                    egui::ComboBox::from_id_salt("IK Rule Kind")
+                        .selected_text(format!("{:?}", ik_rule.kind))
+                        .show_ui(ui, |ui| {
+                            ui.selectable_value(&mut ik_rule.kind, IkRuleKind::Touch, "Touch");
+                            ui.selectable_value(&mut ik_rule.kind, IkRuleKind::Footstep, "Footstep");
+                            ui.selectable_value(&mut ik_rule.kind, IkRuleKind::Release, "Release");
+                            ui.selectable_value(&mut ik_rule.kind, IkRuleKind::Unlatch, "Unlatch");
+                        });
+
+                    if ik_rule.kind == IkRuleKind::Touch {
+                        let bone_label = ui.label("Bone: ");
+                        ui.text_edit_singleline(&mut ik_rule.bone).labelled_by(bone_label.id);
+                    }
+                });
+
+                render_ik_rule_range(ui, ik_rule, last_frame);
+            });
+        }
+
+        if let Some(removed_rule_index) = removed_rule {
+            active_sequence.ik_rules.remove(removed_rule_index);
+        }
+
+        if ui.button("Add IK Rule").clicked() {
+            active_sequence.ik_rules.push(Default::default());
+        }
+    });
+}
+
+fn render_ik_rule_range(ui: &mut egui::Ui, ik_rule: &mut IkRule, last_frame: usize) {
+    ui.indent("IK Rule Range", |ui| {
+        ui.horizontal(|ui| {
+            ui.checkbox(&mut ik_rule.define_range, "Range");
+            ui.add_enabled_ui(ik_rule.define_range, |ui| {
+                ui.label("Start:");
+                ui.add(egui::DragValue::new(&mut ik_rule.start).range(0..=last_frame));
+                ui.label("Peak:");
+                ui.add(egui::DragValue::new(&mut ik_rule.peak).range(ik_rule.start..=last_frame));
+                ui.label("Tail:");
+                ui.add(egui::DragValue::new(&mut ik_rule.tail).range(ik_rule.peak..=last_frame));
+                ui.label("End:");
+                ui.add(egui::DragValue::new(&mut ik_rule.end).range(ik_rule.tail..=last_frame));
+            });
+        });
+
+        if matches!(ik_rule.kind, IkRuleKind::Release | IkRuleKind::Unlatch) {
+            return;
+        }
+
+        ui.horizontal(|ui| {
+            ui.label("Contact:");
+            ui.add(egui::DragValue::new(&mut ik_rule.contact).range(0..=last_frame));
+            ui.label("Height:");
+            ui.add(egui::DragValue::new(&mut ik_rule.height).speed(0.1));
+            ui.label("Radius:");
+            ui.add(egui::DragValue::new(&mut ik_rule.radius).speed(0.1));
+            ui.label("Floor:");
+            ui.add(egui::DragValue::new(&mut ik_rule.floor).speed(0.1));
+        });
+    });
+}
+
+fn render_ik_locks(ui: &mut egui::Ui, active_sequence: &mut Sequence) {
+    egui::CollapsingHeader::new("IK Locks").show(ui, |ui| {
+        let mut removed_lock = None;
+        for (lock_index, ik_lock) in active_sequence.ik_locks.iter_mut().enumerate() {
+            ui.push_id(lock_index, |ui| {
+                ui.horizontal(|ui| {
+                    if ui.add(icon(IconType::Remove)).clicked() {
+                        removed_lock = Some(lock_index);
+                    }
+
+                    let chain_label = ui.label("Chain: ");
+                    ui.text_edit_singleline(&mut ik_lock.chain).labelled_by(chain_label.id);
+                    ui.add(egui::Slider::new(&mut ik_lock.position_weight, 0.0..=1.0).text("Position Weight"))
+                        .on_hover_text("1 Keeps The Foot At Its Locked Position");
+                    ui.add(egui::Slider::new(&mut ik_lock.rotation_weight, 0.0..=1.0).text("Rotation Weight"))
+                        .on_hover_text("1 Keeps The Foot At Its Locked Rotation");
+                });
+            });
+        }
+
+        if let Some(removed_lock_index) = removed_lock {
+            active_sequence.ik_locks.remove(removed_lock_index);
+        }
+
+        if ui.button("Add IK Lock").clicked() {
+            active_sequence.ik_locks.push(Default::default());
+        }
+    });
+}
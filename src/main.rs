@@ -37,9 +37,17 @@ impl Default for SourceWrenchApplication {
 
         let [main_tab, logging_tab] = tree.main_surface_mut().split_right(egui_dock::NodeIndex::root(), 0.5, vec![UniqueTabs::Log]);
 
-        let [_, _] = tree
-            .main_surface_mut()
-            .split_below(main_tab, 0.35, vec![UniqueTabs::ModelGroups, UniqueTabs::Flexing, UniqueTabs::BoneProperties]);
+        let [_, _] = tree.main_surface_mut().split_below(
+            main_tab,
+            0.35,
+            vec![
+                UniqueTabs::ModelGroups,
+                UniqueTabs::Materials,
+                UniqueTabs::KeyValues,
+                UniqueTabs::Flexing,
+                UniqueTabs::BoneProperties,
+            ],
+        );
 
         let [_, _] = tree
             .main_surface_mut()
@@ -1,5 +1,6 @@
 use bitflags::bitflags;
 use indexmap::{IndexMap, IndexSet};
+use std::path::PathBuf;
 use thiserror::Error as ThisError;
 
 use crate::{
@@ -12,28 +13,77 @@ use crate::{
 
 mod animation;
 mod bones;
+mod materials;
 mod mesh;
+mod properties;
 mod sequences;
 
 use animation::{ProcessingAnimationError, process_animations};
+pub use bones::create_skeleton_report;
 use bones::{ProcessingBoneError, process_bones};
+use materials::validate_materials;
 use mesh::{ProcessingMeshError, process_meshes};
-use sequences::{ProcessingSequenceError, process_sequences};
+use properties::{ProcessingPropertiesError, process_model_properties};
+use sequences::{ProcessingSequenceError, process_pose_parameters, process_sequences};
 
 #[derive(Debug, Default)]
 pub struct CompiledData {
+    pub model_properties: ModelProperties,
     pub bone_data: BoneData,
     pub animation_data: AnimationData,
-    pub sequence_data: IndexMap<String, Sequence>,
+    pub pose_parameters: IndexMap<String, PoseParameter>,
+    pub sequence_data: SequenceData,
     pub model_data: ModelData,
 }
 
+#[derive(Debug, Default)]
+pub struct ModelProperties {
+    pub flags: ModelFlags,
+    pub contents: ModelContents,
+    pub surface_property: String,
+    pub eye_position: Vector3,
+    /// The position the model is lit from. None if the center of the model is used.
+    pub illumination_position: Option<Vector3>,
+    pub mass: f64,
+    pub constant_directional_light_dot: u8,
+    /// The key values text of the model.
+    pub keyvalues: String,
+}
+
+bitflags! {
+    #[derive(Debug, Default)]
+    pub struct ModelFlags: i32 {
+        const AUTO_GENERATED_HITBOX          = 0x00000001;
+        const FORCE_OPAQUE                   = 0x00000004;
+        const TRANSLUCENT_TWO_PASS           = 0x00000008;
+        const STATIC_PROP                    = 0x00000010;
+        const OBSOLETE                       = 0x00000200;
+        const NO_FORCED_FADE                 = 0x00000800;
+        const FORCE_PHONEME_CROSS_FADE       = 0x00001000;
+        const CONSTANT_DIRECTIONAL_LIGHT_DOT = 0x00002000;
+        const AMBIENT_BOOST                  = 0x00010000;
+        const DO_NOT_CAST_SHADOWS            = 0x00020000;
+        const CAST_TEXTURE_SHADOWS           = 0x00040000;
+    }
+
+    #[derive(Debug, Default)]
+    pub struct ModelContents: i32 {
+        const SOLID   = 0x00000001;
+        const GRATE   = 0x00000008;
+        const MONSTER = 0x02000000;
+        const LADDER  = 0x20000000;
+    }
+}
+
 #[derive(Debug, Default)]
 pub struct BoneData {
     pub processed_bones: IndexMap<String, Bone>,
     /// Indexes of all processed bones sorted by name.
     pub sorted_bones_by_name: Vec<u8>,
     pub ik_chains: IndexMap<String, IKChain>,
+    pub bone_controllers: Vec<BoneController>,
+    /// The rules used to match the source file bones to the processed bones.
+    pub bone_renames: bones::BoneRenames,
 }
 
 #[derive(Debug, Default)]
@@ -48,11 +98,93 @@ pub struct Bone {
     pub flags: BoneFlags,
     /// The transforms in world space.
     pub world_transform: Matrix4,
+    /// The procedural data that drives the bone.
+    pub procedural: Option<ProceduralBone>,
+}
+
+#[derive(Debug)]
+pub enum ProceduralBone {
+    Jiggle(JiggleBone),
+    Quaternion(QuaternionBone),
+    AimAt(AimAtBone),
+}
+
+#[derive(Debug, Default)]
+pub struct QuaternionBone {
+    /// The index of the bone whose rotation is matched to the triggers.
+    pub control: usize,
+    pub triggers: Vec<QuaternionTrigger>,
+}
+
+#[derive(Debug, Default)]
+pub struct QuaternionTrigger {
+    /// The inverse of the tolerance in radians.
+    pub inverse_tolerance: f64,
+    /// The rotation of the control bone.
+    pub trigger: Quaternion,
+    /// The location of the driven bone, with the base position applied.
+    pub position: Vector3,
+    /// The rotation of the driven bone.
+    pub rotation: Quaternion,
+}
+
+#[derive(Debug, Default)]
+pub struct AimAtBone {
+    /// The index of the parent of the driven bone.
+    pub parent: usize,
+    /// The index of the bone to point at.
+    pub aim: usize,
+    pub aim_vector: Vector3,
+    pub up_vector: Vector3,
+    pub base_position: Vector3,
+}
+
+/// The jiggle bone with all angles in radians.
+#[derive(Debug, Default)]
+pub struct JiggleBone {
+    pub flags: JiggleFlags,
+    pub length: f64,
+    pub tip_mass: f64,
+    pub yaw_stiffness: f64,
+    pub yaw_damping: f64,
+    pub pitch_stiffness: f64,
+    pub pitch_damping: f64,
+    pub along_stiffness: f64,
+    pub along_damping: f64,
+    pub angle_limit: f64,
+    pub minimum_yaw: f64,
+    pub maximum_yaw: f64,
+    pub yaw_friction: f64,
+    pub yaw_bounce: f64,
+    pub minimum_pitch: f64,
+    pub maximum_pitch: f64,
+    pub pitch_friction: f64,
+    pub pitch_bounce: f64,
+    pub base_mass: f64,
+    pub base_stiffness: f64,
+    pub base_damping: f64,
+    pub base_minimum: Vector3,
+    pub base_maximum: Vector3,
+    pub base_friction: Vector3,
 }
 
 bitflags! {
     #[derive(Debug, Default)]
+    pub struct JiggleFlags: i32 {
+        const FLEXIBLE          = 0x01;
+        const RIGID             = 0x02;
+        const YAW_CONSTRAINT    = 0x04;
+        const PITCH_CONSTRAINT  = 0x08;
+        const ANGLE_CONSTRAINT  = 0x10;
+        const LENGTH_CONSTRAINT = 0x20;
+        const BASE_SPRING       = 0x40;
+    }
+}
+
+bitflags! {
+    #[derive(Clone, Copy, Debug, Default)]
     pub struct BoneFlags: i32 {
+        const ALWAYS_PROCEDURAL  = 0x00000004;
         const USED_BY_VERTEX     = 0x00000400;
         const USED_BY_HITBOX     = 0x00000100;
         const USED_BY_ATTACHMENT = 0x00000200;
@@ -61,6 +193,59 @@ bitflags! {
     }
 }
 
+/// The sources of every bone across the loaded files.
+#[derive(Clone, Debug, Default)]
+pub struct SkeletonReport {
+    pub bones: IndexMap<String, Vec<BoneSource>>,
+}
+
+impl SkeletonReport {
+    /// Returns the bones that differ between source files.
+    pub fn mismatched_bones(&self) -> impl Iterator<Item = (&String, &Vec<BoneSource>)> {
+        self.bones.iter().filter(|(_, sources)| sources.iter().any(|source| source.mismatched))
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct BoneSource {
+    /// The file that defines the bone.
+    pub file: PathBuf,
+    /// The name of the parent bone in the file. None if the bone is a root bone.
+    pub parent: Option<String>,
+    /// The distance to the bone location in the first file.
+    pub location_delta: f64,
+    /// The angle in degrees to the bone rotation in the first file.
+    pub rotation_delta: f64,
+    /// The parent is different or a delta is larger than the tolerances.
+    pub mismatched: bool,
+}
+
+#[derive(Debug, Default)]
+pub struct BoneController {
+    /// The index of the bone that is moved.
+    pub bone: usize,
+    pub flags: BoneControllerFlags,
+    pub start: f64,
+    pub end: f64,
+    /// The controller value at rest remapped from 0 to 255.
+    pub rest: i32,
+    /// The controller index the game sets, 4 is the mouth.
+    pub input_field: i32,
+}
+
+bitflags! {
+    #[derive(Clone, Copy, Debug, Default)]
+    pub struct BoneControllerFlags: i32 {
+        const X             = 0x0001;
+        const Y             = 0x0002;
+        const Z             = 0x0004;
+        const XR            = 0x0008;
+        const YR            = 0x0010;
+        const ZR            = 0x0020;
+        const ROTATION_LOOP = 0x8000;
+    }
+}
+
 #[derive(Debug, Default)]
 pub struct IKChain {
     /// The bone indexes for hip/knee/foot.
@@ -86,17 +271,102 @@ pub struct AnimationData {
     pub processed_animations: IndexMap<String, Animation>,
     /// The scales for location an rotation for the run length encoding.
     pub animation_scales: Vec<(Vector3, Vector3)>,
+    /// The scales the animations would use if they were not compressed. None if the animations are not compressed.
+    pub uncompressed_animation_scales: Option<Vec<(Vector3, Vector3)>>,
     /// Used by sequence to get the correct animation to the processed animations.
     pub remapped_animations: IndexMap<usize, usize>,
+    /// The size in bytes of each block in the ani file. None if the animations are stored in the model.
+    pub animation_block_size: Option<usize>,
 }
 
 #[derive(Debug, Default)]
 pub struct Animation {
     pub frame_count: usize,
+    pub frame_rate: f32,
+    pub flags: AnimationFlags,
+    pub movements: Vec<Movement>,
+    pub section_frame_count: usize,
     pub sections: Vec<Vec<AnimatedBoneData>>,
+    /// The sections before compression, kept to report the size the compression saved.
+    pub uncompressed_sections: Option<Vec<Vec<AnimatedBoneData>>>,
+    /// The ik rules of the sequence that uses the animation.
+    pub ik_rules: Vec<IkRule>,
+}
+
+#[derive(Debug, Default)]
+pub struct IkRule {
+    pub kind: IkRuleKind,
+    /// The index of the ik chain.
+    pub chain: usize,
+    /// The index of the bone the chain touches. None if the rule is not a touch rule.
+    pub bone: Option<usize>,
+    pub height: f64,
+    pub radius: f64,
+    pub floor: f64,
+    /// The transform of the foot at the contact frame.
+    pub position: Vector3,
+    pub rotation: Quaternion,
+    /// The frame the error starts at.
+    pub start_frame: usize,
+    /// The cycles of the blend points and the contact frame.
+    pub start: f64,
+    pub peak: f64,
+    pub tail: f64,
+    pub end: f64,
+    pub contact: f64,
+    /// How far the foot goes below and above the contact height.
+    pub drop: f64,
+    pub top: f64,
+    /// The transform of the foot for every frame from the start to the end, relative to the touched bone for touch rules.
+    pub errors: Vec<(Vector3, Quaternion)>,
+}
+
+#[derive(Debug, Default)]
+pub enum IkRuleKind {
+    Touch,
+    #[default]
+    Footstep,
+    Release,
+    Unlatch,
+}
+
+bitflags! {
+    #[derive(Debug, Default)]
+    pub struct AnimationFlags: i32 {
+        const LOOPING = 0x0001;
+        const DELTA   = 0x0004;
+    }
 }
 
 #[derive(Debug, Default)]
+pub struct Movement {
+    /// The last frame of the movement block.
+    pub end_frame: usize,
+    pub flags: MovementFlags,
+    /// The speed at the start and end of the block in units per second.
+    pub velocity: f64,
+    /// The yaw in degrees at the end of the block.
+    pub angle: f64,
+    /// The movement of the block relative to the yaw at the start of the block.
+    pub vector: Vector3,
+    /// The position at the end of the block relative to the start of the animation.
+    pub position: Vector3,
+}
+
+bitflags! {
+    #[derive(Debug, Default, Clone, Copy)]
+    pub struct MovementFlags: i32 {
+        const LX     = 0x0040;
+        const LY     = 0x0080;
+        const LZ     = 0x0100;
+        const LXR    = 0x0200;
+        const LYR    = 0x0400;
+        const LZR    = 0x0800;
+        const LINEAR = 0x1000;
+    }
+}
+
+#[derive(Clone, Debug, Default)]
 pub struct AnimatedBoneData {
     pub bone: u8,
     pub raw_position: Vec<Vector3>,
@@ -105,9 +375,101 @@ pub struct AnimatedBoneData {
     pub delta_rotation: Vec<Quaternion>,
 }
 
+#[derive(Debug, Default)]
+pub struct SequenceData {
+    pub processed_sequences: IndexMap<String, Sequence>,
+    /// The names of the nodes sequences transition between.
+    pub nodes: IndexSet<String>,
+    /// The next node to go to for every node to every other node, indexed by the from node then the to node.
+    /// The nodes start at one as zero is no node.
+    pub node_transitions: Vec<Vec<u8>>,
+}
+
 #[derive(Debug, Default)]
 pub struct Sequence {
     pub animations: Vec<Vec<i16>>,
+    /// The pose parameters that blend the columns and rows. None if the axis is not blended.
+    pub blend_axes: [Option<BlendAxis>; 2],
+    pub flags: SequenceFlags,
+    /// The activity name of the sequence. None if the sequence has no activity.
+    pub activity: Option<(String, i32)>,
+    pub fade_in_time: f64,
+    pub fade_out_time: f64,
+    /// The entry and exit node index starting at one, zero is no node.
+    pub entry_node: usize,
+    pub exit_node: usize,
+    pub reverse_transition: bool,
+    /// The events sorted by cycle.
+    pub events: Vec<SequenceEvent>,
+    pub auto_layers: Vec<AutoLayer>,
+    /// The weight of every bone.
+    pub weight_list: Vec<f64>,
+    /// The amount of ik rules on every animation of the sequence.
+    pub ik_rule_count: usize,
+    pub ik_locks: Vec<IkLock>,
+}
+
+#[derive(Debug, Default)]
+pub struct AutoLayer {
+    /// The index of the layered sequence.
+    pub sequence: usize,
+    /// The index of the pose parameter that drives the blend. None if the cycle drives the blend.
+    pub pose_parameter: Option<usize>,
+    pub flags: AutoLayerFlags,
+    /// The blend points from 0 to 1 in the cycle or the pose parameter range.
+    pub start: f64,
+    pub peak: f64,
+    pub tail: f64,
+    pub end: f64,
+}
+
+bitflags! {
+    #[derive(Debug, Default)]
+    pub struct AutoLayerFlags: i32 {
+        const SPLINE   = 0x0040;
+        const XFADE    = 0x0080;
+        const NO_BLEND = 0x0200;
+        const LOCAL    = 0x1000;
+        const POSE     = 0x4000;
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct SequenceEvent {
+    pub cycle: f64,
+    /// The number of the event. None if the event is named.
+    pub id: Option<i32>,
+    pub name: String,
+    pub options: String,
+}
+
+bitflags! {
+    #[derive(Debug, Default)]
+    pub struct SequenceFlags: i32 {
+        const LOOPING   = 0x0001;
+        const SNAP      = 0x0002;
+        const DELTA     = 0x0004;
+        const AUTO_PLAY = 0x0008;
+        const REALTIME  = 0x0100;
+        const LOCAL     = 0x0200;
+        const HIDDEN    = 0x0400;
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct BlendAxis {
+    /// The index of the pose parameter.
+    pub parameter: usize,
+    pub start: f64,
+    pub end: f64,
+}
+
+#[derive(Debug, Default)]
+pub struct PoseParameter {
+    pub start: f64,
+    pub end: f64,
+    /// The range the value wraps around at. None if the value does not loop.
+    pub loop_range: Option<f64>,
 }
 
 #[derive(Debug, Default)]
@@ -116,6 +478,8 @@ pub struct ModelData {
     pub bounding_box: BoundingBox,
     pub hitboxes: IndexMap<usize, BoundingBox>,
     pub materials: IndexSet<String>,
+    /// The directories to search for the materials.
+    pub material_paths: IndexSet<String>,
     pub flex_data: FlexData,
 }
 
@@ -206,6 +570,8 @@ pub enum ProcessingDataError {
     NoSequences,
     #[error("Model Has No Animations")]
     NoAnimations,
+    #[error("Failed To Process Model Properties: {0}")]
+    ProcessingPropertiesError(#[from] ProcessingPropertiesError),
     #[error("Failed To Process Bone Data: {0}")]
     ProcessingBoneError(#[from] ProcessingBoneError),
     #[error("Failed To Process Animation Data: {0}")]
@@ -223,6 +589,9 @@ pub const VERTEX_CACHE_SIZE: usize = 16;
 pub const FLOAT_TOLERANCE: f64 = f32::EPSILON as f64;
 
 pub fn compile_data(input_data: &input::SourceInput, source_files: &FileManager) -> Result<CompiledData, ProcessingDataError> {
+    debug!("Processing Model Properties.");
+    let processed_model_properties = process_model_properties(input_data)?;
+
     debug!("Processing Bones.");
     let processed_bone_data = process_bones(input_data, source_files)?;
     info!("Model uses {} bones.", processed_bone_data.processed_bones.len());
@@ -232,7 +601,7 @@ pub fn compile_data(input_data: &input::SourceInput, source_files: &FileManager)
     }
 
     debug!("Processing Animations.");
-    let processed_animation_data = process_animations(input_data, source_files, &processed_bone_data)?;
+    let mut processed_animation_data = process_animations(input_data, source_files, &processed_bone_data)?;
     verbose!("Model has {} animations.", processed_animation_data.processed_animations.len());
 
     if processed_animation_data.processed_animations.is_empty() {
@@ -240,10 +609,13 @@ pub fn compile_data(input_data: &input::SourceInput, source_files: &FileManager)
     }
 
     debug!("Processing Sequences.");
-    let processed_sequences = process_sequences(input_data, &processed_animation_data.remapped_animations)?;
-    info!("Model has {} sequences.", processed_sequences.len());
+    let processed_pose_parameters = process_pose_parameters(input_data)?;
+    verbose!("Model has {} pose parameters.", processed_pose_parameters.len());
+
+    let processed_sequences = process_sequences(input_data, &processed_bone_data, &mut processed_animation_data, &processed_pose_parameters)?;
+    info!("Model has {} sequences.", processed_sequences.processed_sequences.len());
 
-    if processed_sequences.is_empty() {
+    if processed_sequences.processed_sequences.is_empty() {
         return Err(ProcessingDataError::NoSequences);
     }
 
@@ -252,9 +624,16 @@ pub fn compile_data(input_data: &input::SourceInput, source_files: &FileManager)
     verbose!("Model has {} materials.", processed_mesh.materials.len());
     info!("Model has {} model groups.", processed_mesh.model_groups.len());
 
+    if let Some(content_directory) = &input_data.content_directory {
+        debug!("Validating Materials.");
+        validate_materials(content_directory, &processed_mesh, &processed_model_properties);
+    }
+
     Ok(CompiledData {
+        model_properties: processed_model_properties,
         bone_data: processed_bone_data,
         animation_data: processed_animation_data,
+        pose_parameters: processed_pose_parameters,
         sequence_data: processed_sequences,
         model_data: processed_mesh,
     })
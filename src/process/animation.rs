@@ -1,9 +1,14 @@
 use indexmap::IndexMap;
+use std::{
+    f64::consts::{PI, TAU},
+    ops::{Add, Mul},
+    sync::Arc,
+};
 use thiserror::Error as ThisError;
 
 use crate::{
     debug,
-    import::FileManager,
+    import::{self, FileData, FileManager},
     input,
     utilities::mathematics::{EULER_ROTATION, Matrix4, Quaternion, Vector3, create_space_transform},
     warn,
@@ -17,8 +22,25 @@ pub enum ProcessingAnimationError {
     FileSourceNotLoaded,
     #[error("Model Has Too Many Animations")]
     TooManyAnimations,
+    #[error("Animation \"{0}\" Frame Range Is Outside The Source Animation")]
+    InvalidFrameRange(String),
+    #[error("Animation \"{0}\" Frame Rate Must Be Greater Than Zero")]
+    InvalidFrameRate(String),
+    #[error("Animation \"{0}\" Subtract Animation Does Not Exist")]
+    SubtractAnimationNotFound(String),
+    #[error("Animation \"{0}\" Subtract Frame Is Outside The Subtract Animation")]
+    InvalidSubtractFrame(String),
+    #[error("Animation \"{0}\" Section Frames Must Be Greater Than Zero")]
+    InvalidSectionFrames(String),
+    #[error("Animation Compression Tolerance Can Not Be Negative")]
+    NegativeCompressionTolerance,
+    #[error("Animation Block Size Must Be Greater Than Zero")]
+    InvalidAnimationBlockSize,
 }
 
+/// The frame rate used if the animation or source file does not define one.
+const DEFAULT_FRAME_RATE: f32 = 30.0;
+
 pub fn process_animations(
     input_data: &input::SourceInput,
     source_files: &FileManager,
@@ -27,6 +49,21 @@ pub fn process_animations(
     let mut remapped_animations = IndexMap::new();
     let mut processed_animations = IndexMap::new();
     let mut model_frame_count = 0;
+
+    let animation_settings = &input_data.animation_settings;
+    if animation_settings.compress && (animation_settings.position_tolerance < 0.0 || animation_settings.rotation_tolerance < 0.0) {
+        return Err(ProcessingAnimationError::NegativeCompressionTolerance);
+    }
+
+    let animation_block_size = if animation_settings.external_animation_blocks {
+        if animation_settings.animation_block_size == 0 {
+            return Err(ProcessingAnimationError::InvalidAnimationBlockSize);
+        }
+        Some(animation_settings.animation_block_size * 1024)
+    } else {
+        None
+    };
+
     for imputed_animation in &input_data.animations {
         // Check if the animation is used in any sequence.
         if !input_data
@@ -43,118 +80,77 @@ pub fn process_animations(
         debug_assert!(!processed_animations.contains_key(&processed_animation_name));
 
         // Gather imported animation data.
-        let imported_file = source_files
-            .get_file_data(imputed_animation.source_file_path.as_ref().ok_or(ProcessingAnimationError::NoFileSource)?)
-            .ok_or(ProcessingAnimationError::FileSourceNotLoaded)?;
+        let imported_file = load_imported_file(imputed_animation, source_files)?;
         let imported_animation = &imported_file.animations[imputed_animation.source_animation];
 
-        let frame_count = imported_animation.frame_count.get();
-        model_frame_count += frame_count;
-
-        // All the import bones with all frames of animation global transforms.
-        let mut imported_bone_animation_transforms: Vec<Vec<Matrix4>> = Vec::with_capacity(imported_file.skeleton.len());
-        for (import_bone_index, import_bone) in imported_file.skeleton.values().enumerate() {
-            /// Convert channel keyframes to a continuous set of values.
-            fn bake_channel_keyframes<T: Copy>(channel: &IndexMap<usize, T>, frame_count: usize, default: T) -> Vec<T> {
-                let mut baked_channel = Vec::with_capacity(frame_count);
-
-                for frame in 0..frame_count {
-                    if let Some(keyframe) = channel.get(&frame) {
-                        baked_channel.push(*keyframe);
-                        continue;
-                    }
-
-                    if let Some(last_value) = baked_channel.last() {
-                        baked_channel.push(*last_value);
-                        continue;
-                    }
-
-                    baked_channel.push(default);
-                }
-
-                baked_channel
-            }
-
-            let location_channel = match imported_animation.channels.get(&import_bone_index) {
-                Some(import_channel) => bake_channel_keyframes(&import_channel.location, frame_count, import_bone.location),
-                None => vec![import_bone.location; frame_count],
-            };
-            let rotation_channel = match imported_animation.channels.get(&import_bone_index) {
-                Some(import_channel) => bake_channel_keyframes(&import_channel.rotation, frame_count, import_bone.rotation),
-                None => vec![import_bone.rotation; frame_count],
-            };
-
-            let mut imported_animation_transform = Vec::with_capacity(frame_count);
-            for frame in 0..frame_count {
-                let location = location_channel[frame];
-                let rotation = rotation_channel[frame];
-                let transform = Matrix4::from_rotation_translation(rotation, location);
-
-                if let Some(parent_transform) = import_bone.parent.map(|parent_index| imported_bone_animation_transforms[parent_index][frame]) {
-                    imported_animation_transform.push(parent_transform * transform);
-                    continue;
-                }
+        let frame_rate = if imputed_animation.define_frame_rate {
+            imputed_animation.frame_rate
+        } else {
+            imported_animation.frame_rate.unwrap_or(DEFAULT_FRAME_RATE)
+        };
 
-                let space_transform = create_space_transform(imported_file.up, imported_file.forward);
-                imported_animation_transform.push(space_transform.inverse() * transform);
-            }
-            imported_bone_animation_transforms.push(imported_animation_transform);
+        if !frame_rate.is_finite() || frame_rate <= 0.0 {
+            return Err(ProcessingAnimationError::InvalidFrameRate(imputed_animation.name.clone()));
         }
 
-        // All the proceed bones with all frames of animation global transforms using import animation.
-        let mut processed_bone_animation_transforms: Vec<Vec<Matrix4>> = Vec::with_capacity(processed_bone_data.processed_bones.len());
-        for (processed_bone_name, processed_bone) in &processed_bone_data.processed_bones {
-            if let Some(imported_animation_transform) = imported_file
-                .skeleton
-                .get_index_of(processed_bone_name)
-                .map(|import_bone_index| &imported_bone_animation_transforms[import_bone_index])
-            {
-                processed_bone_animation_transforms.push(imported_animation_transform.clone());
-                continue;
-            }
+        let mut animation_flags = super::AnimationFlags::empty();
+        animation_flags.set(super::AnimationFlags::LOOPING, imputed_animation.looping);
 
-            if let Some(processed_parent_bone_animation_transform) = processed_bone
-                .parent
-                .map(|processed_bone_parent_index| &processed_bone_animation_transforms[processed_bone_parent_index])
-            {
-                processed_bone_animation_transforms.push(
-                    processed_parent_bone_animation_transform
-                        .iter()
-                        .map(|parent_transform| parent_transform * Matrix4::from_rotation_translation(processed_bone.rotation, processed_bone.location))
-                        .collect(),
-                );
-                continue;
+        let mut processed_bone_animation_local_transforms = create_local_transforms(imputed_animation, &imported_file, processed_bone_data)?;
+        let frame_count = processed_bone_animation_local_transforms[0].len();
+        model_frame_count += frame_count;
+
+        let movements = extract_motion(
+            imputed_animation,
+            frame_rate,
+            processed_bone_data,
+            &mut processed_bone_animation_local_transforms,
+        );
+
+        if imputed_animation.define_subtract {
+            let reference_animation = input_data
+                .animations
+                .iter()
+                .find(|animation| animation.animation_identifier == imputed_animation.subtract_animation)
+                .ok_or(ProcessingAnimationError::SubtractAnimationNotFound(imputed_animation.name.clone()))?;
+
+            let reference_file = load_imported_file(reference_animation, source_files)?;
+            let reference_local_transforms = create_local_transforms(reference_animation, &reference_file, processed_bone_data)?;
+
+            if imputed_animation.subtract_frame >= reference_local_transforms[0].len() {
+                return Err(ProcessingAnimationError::InvalidSubtractFrame(imputed_animation.name.clone()));
             }
 
-            processed_bone_animation_transforms.push(vec![processed_bone.world_transform; frame_count]);
-        }
+            debug!(
+                "Subtracting frame {} of \"{}\" from \"{}\"",
+                imputed_animation.subtract_frame, reference_animation.name, imputed_animation.name
+            );
 
-        // TODO: Implement animation processing.
-        // TODO: Add a check if the position data is going to be out of bounds.
-
-        // All the proceed bones with all frames of animation local transforms.
-        let mut processed_bone_animation_local_transforms: Vec<Vec<Matrix4>> = Vec::with_capacity(processed_bone_data.processed_bones.len());
-        for (processed_bone_index, processed_bone) in processed_bone_data.processed_bones.values().enumerate() {
-            let mut processed_bone_animation_local_transform = Vec::with_capacity(frame_count);
-            let processed_bone_animation_transform = &processed_bone_animation_transforms[processed_bone_index];
-            if let Some(processed_parent_bone_animation_transform) = processed_bone
-                .parent
-                .map(|processed_bone_parent_index| &processed_bone_animation_transforms[processed_bone_parent_index])
+            // The deltas are in the local space of each bone.
+            for (bone_local_transforms, reference_bone_local_transforms) in
+                processed_bone_animation_local_transforms.iter_mut().zip(&reference_local_transforms)
             {
-                for frame in 0..frame_count {
-                    let parent_transform = processed_parent_bone_animation_transform[frame];
-                    let transform = processed_bone_animation_transform[frame];
-                    processed_bone_animation_local_transform.push(parent_transform.inverse() * transform);
+                let (_, reference_rotation, reference_location) =
+                    reference_bone_local_transforms[imputed_animation.subtract_frame].to_scale_rotation_translation();
+                for local_transform in bone_local_transforms {
+                    let (_, rotation, location) = local_transform.to_scale_rotation_translation();
+                    *local_transform = Matrix4::from_rotation_translation(reference_rotation.inverse() * rotation, location - reference_location);
                 }
-                processed_bone_animation_local_transforms.push(processed_bone_animation_local_transform);
-                continue;
             }
-            processed_bone_animation_local_transforms.push(processed_bone_animation_transform.clone());
+
+            animation_flags.insert(super::AnimationFlags::DELTA);
         }
 
         // Split animation into sections
-        let frames_per_sections = 30; // TODO: Make this configurable.
-        let animation_section_split_threshold = 120; // TODO: Make this configurable.
+        let (frames_per_sections, animation_section_split_threshold) = if imputed_animation.define_sections {
+            (imputed_animation.section_frames, imputed_animation.section_threshold)
+        } else {
+            (animation_settings.section_frames, animation_settings.section_threshold)
+        };
+
+        if frames_per_sections == 0 {
+            return Err(ProcessingAnimationError::InvalidSectionFrames(imputed_animation.name.clone()));
+        }
 
         let section_count = if frame_count >= animation_section_split_threshold {
             (frame_count / frames_per_sections) + 2
@@ -169,14 +165,24 @@ pub fn process_animations(
 
         let mut processed_animation = super::Animation {
             frame_count,
+            frame_rate,
+            flags: animation_flags,
+            movements,
+            section_frame_count,
             sections: Vec::with_capacity(section_count),
+            uncompressed_sections: None,
+            ik_rules: Vec::new(),
         };
 
+        let is_delta = processed_animation.flags.contains(super::AnimationFlags::DELTA);
+        let mut uncompressed_sections = Vec::with_capacity(section_count);
+
         for section in 0..section_count {
             let section_frame_start = (section * section_frame_count).min(frame_count - 1);
             let section_frame_end = ((section + 1) * section_frame_count).min(frame_count - 1);
 
             let mut section_data = Vec::with_capacity(processed_bone_data.processed_bones.len());
+            let mut uncompressed_section_data = Vec::new();
             for (index_bone, channel_data) in processed_bone_animation_local_transforms.iter().enumerate() {
                 let bone = &processed_bone_data.processed_bones[index_bone];
                 let mut raw_position = Vec::with_capacity(section_frame_count);
@@ -184,27 +190,52 @@ pub fn process_animations(
                 let mut delta_position = Vec::with_capacity(section_frame_count);
                 let mut delta_rotation = Vec::with_capacity(section_frame_count);
 
-                // TODO: If animation is delta then skip subtracting from bone
                 for frame in channel_data.iter().take(section_frame_end + 1).skip(section_frame_start) {
                     let (_, rotation, location) = frame.to_scale_rotation_translation();
                     raw_position.push(location);
                     raw_rotation.push(rotation);
+
+                    // Delta animations are already relative so the bone is not subtracted.
+                    if is_delta {
+                        delta_position.push(location);
+                        delta_rotation.push(rotation);
+                        continue;
+                    }
+
                     delta_position.push(location - bone.location);
                     let rotation_euler = Vector3::from(rotation.to_euler(EULER_ROTATION));
                     let delta_euler = rotation_euler - Vector3::from(bone.rotation.to_euler(EULER_ROTATION));
                     delta_rotation.push(Quaternion::from_euler(EULER_ROTATION, delta_euler.x, delta_euler.y, delta_euler.z));
                 }
 
-                section_data.push(super::AnimatedBoneData {
+                let mut bone_data = super::AnimatedBoneData {
                     bone: index_bone as u8,
                     raw_position,
                     raw_rotation,
                     delta_position,
                     delta_rotation,
-                });
+                };
+
+                if animation_settings.compress {
+                    uncompressed_section_data.push(bone_data.clone());
+                    compress_bone_data(
+                        &mut bone_data,
+                        bone,
+                        is_delta,
+                        animation_settings.position_tolerance,
+                        animation_settings.rotation_tolerance.to_radians(),
+                    );
+                }
+
+                section_data.push(bone_data);
             }
 
             processed_animation.sections.push(section_data);
+            uncompressed_sections.push(uncompressed_section_data);
+        }
+
+        if animation_settings.compress {
+            processed_animation.uncompressed_sections = Some(uncompressed_sections);
         }
 
         processed_animations.insert(processed_animation_name, processed_animation);
@@ -216,48 +247,512 @@ pub fn process_animations(
         return Err(ProcessingAnimationError::TooManyAnimations);
     }
 
-    let mut animation_scales = vec![(Vector3::default(), Vector3::default()); processed_bone_data.processed_bones.len()];
-    for processed_animation in processed_animations.values() {
-        for sections in &processed_animation.sections {
-            for section in sections {
-                for position in &section.delta_position {
-                    for axis in 0..3 {
-                        let value = position[axis].abs();
-                        if value > animation_scales[section.bone as usize].0[axis] {
-                            animation_scales[section.bone as usize].0[axis] = value;
-                        }
-                    }
-                }
+    let bone_count = processed_bone_data.processed_bones.len();
+    let animation_scales = create_animation_scales(processed_animations.values().map(|animation| &animation.sections), bone_count);
 
-                for rotation in &section.delta_rotation {
-                    let (roll, pitch, yaw) = rotation.to_euler(EULER_ROTATION);
+    // The scales the animations would be written with if they were not compressed, used to report the saved size.
+    let uncompressed_animation_scales = animation_settings.compress.then(|| {
+        create_animation_scales(
+            processed_animations
+                .values()
+                .map(|animation| animation.uncompressed_sections.as_ref().unwrap_or(&animation.sections)),
+            bone_count,
+        )
+    });
 
-                    if roll.abs() > animation_scales[section.bone as usize].1[0] {
-                        animation_scales[section.bone as usize].1[0] = roll.abs();
-                    }
+    Ok(super::AnimationData {
+        processed_animations,
+        animation_scales,
+        uncompressed_animation_scales,
+        remapped_animations,
+        animation_block_size,
+    })
+}
 
-                    if pitch.abs() > animation_scales[section.bone as usize].1[1] {
-                        animation_scales[section.bone as usize].1[1] = pitch.abs();
-                    }
+/// Creates the scale of every bone to fit the largest delta position and rotation of the sections in the range of a short.
+fn create_animation_scales<'a>(animations: impl Iterator<Item = &'a Vec<Vec<super::AnimatedBoneData>>>, bone_count: usize) -> Vec<(Vector3, Vector3)> {
+    let mut animation_scales = vec![(Vector3::default(), Vector3::default()); bone_count];
+    for sections in animations {
+        for section in sections.iter().flatten() {
+            let (position_scale, rotation_scale) = &mut animation_scales[section.bone as usize];
 
-                    if yaw.abs() > animation_scales[section.bone as usize].1[2] {
-                        animation_scales[section.bone as usize].1[2] = yaw.abs();
-                    }
-                }
+            for position in &section.delta_position {
+                *position_scale = position_scale.max(position.abs());
+            }
+
+            for rotation in &section.delta_rotation {
+                *rotation_scale = rotation_scale.max(Vector3::from(rotation.to_euler(EULER_ROTATION)).abs());
             }
         }
     }
 
     for (position, rotation) in &mut animation_scales {
+        *position /= i16::MAX as f64;
+        *rotation /= i16::MAX as f64;
+    }
+
+    animation_scales
+}
+
+/// Flattens the animated axes of the bone that stay within the tolerance of a held value.
+///
+/// An axis is only flattened if every reconstructed frame stays within the tolerance of the original frame.
+fn compress_bone_data(bone_data: &mut super::AnimatedBoneData, bone: &super::Bone, is_delta: bool, position_tolerance: f64, rotation_tolerance: f64) {
+    let original_positions = bone_data.delta_position.clone();
+    for axis in 0..3 {
+        let Some(held_value) = find_held_value(bone_data.delta_position.iter().map(|position| position[axis]), position_tolerance) else {
+            continue;
+        };
+
+        let mut flattened_positions = bone_data.delta_position.clone();
+        for position in &mut flattened_positions {
+            position[axis] = held_value;
+        }
+
+        if flattened_positions
+            .iter()
+            .zip(&original_positions)
+            .all(|(flattened, original)| flattened.distance(*original) <= position_tolerance)
+        {
+            bone_data.delta_position = flattened_positions;
+        }
+    }
+
+    let mut rotations = bone_data
+        .delta_rotation
+        .iter()
+        .map(|rotation| Vector3::from(rotation.to_euler(EULER_ROTATION)))
+        .collect::<Vec<_>>();
+    for axis in 0..3 {
+        let Some(held_value) = find_held_value(rotations.iter().map(|rotation| rotation[axis]), rotation_tolerance) else {
+            continue;
+        };
+
+        let mut flattened_rotations = rotations.clone();
+        for rotation in &mut flattened_rotations {
+            rotation[axis] = held_value;
+        }
+
+        // The euler axes affect each other, so the error is checked on the rotation the angles create.
+        if flattened_rotations.iter().zip(&bone_data.delta_rotation).all(|(flattened, original)| {
+            Quaternion::from_euler(EULER_ROTATION, flattened.x, flattened.y, flattened.z).angle_between(*original) <= rotation_tolerance
+        }) {
+            rotations = flattened_rotations;
+        }
+    }
+
+    bone_data.delta_rotation = rotations
+        .iter()
+        .map(|rotation| Quaternion::from_euler(EULER_ROTATION, rotation.x, rotation.y, rotation.z))
+        .collect();
+
+    // The raw values are used when the bone does not move, so they must match the compressed deltas.
+    if is_delta {
+        bone_data.raw_position = bone_data.delta_position.clone();
+        bone_data.raw_rotation = bone_data.delta_rotation.clone();
+        return;
+    }
+
+    let bone_rotation = Vector3::from(bone.rotation.to_euler(EULER_ROTATION));
+    bone_data.raw_position = bone_data.delta_position.iter().map(|position| bone.location + position).collect();
+    bone_data.raw_rotation = rotations
+        .iter()
+        .map(|rotation| {
+            let rotation = bone_rotation + rotation;
+            Quaternion::from_euler(EULER_ROTATION, rotation.x, rotation.y, rotation.z)
+        })
+        .collect();
+}
+
+/// Returns the value every value of the axis is within the tolerance of. None if the axis moves more than the tolerance.
+fn find_held_value(values: impl Iterator<Item = f64> + Clone, tolerance: f64) -> Option<f64> {
+    let minimum = values.clone().fold(f64::INFINITY, f64::min);
+    let maximum = values.fold(f64::NEG_INFINITY, f64::max);
+
+    if maximum - minimum > tolerance * 2.0 {
+        return None;
+    }
+
+    // Axes that stay near zero become zero so they are not written.
+    if minimum.abs() <= tolerance && maximum.abs() <= tolerance {
+        return Some(0.0);
+    }
+
+    Some((minimum + maximum) / 2.0)
+}
+
+fn load_imported_file(input_animation: &input::Animation, source_files: &FileManager) -> Result<Arc<FileData>, ProcessingAnimationError> {
+    source_files
+        .get_file_data(input_animation.source_file_path.as_ref().ok_or(ProcessingAnimationError::NoFileSource)?)
+        .ok_or(ProcessingAnimationError::FileSourceNotLoaded)
+}
+
+/// Creates the local transforms of every processed bone for every frame of the animation.
+fn create_local_transforms(
+    input_animation: &input::Animation,
+    imported_file: &FileData,
+    processed_bone_data: &super::BoneData,
+) -> Result<Vec<Vec<Matrix4>>, ProcessingAnimationError> {
+    let imported_animation = &imported_file.animations[input_animation.source_animation];
+    let source_frame_count = imported_animation.frame_count.get();
+
+    // The source frames used by the animation in the order they are played.
+    let mut frames = if input_animation.define_frame_range {
+        if input_animation.start_frame > input_animation.end_frame || input_animation.end_frame >= source_frame_count {
+            return Err(ProcessingAnimationError::InvalidFrameRange(input_animation.name.clone()));
+        }
+        (input_animation.start_frame..=input_animation.end_frame).collect::<Vec<_>>()
+    } else {
+        (0..source_frame_count).collect::<Vec<_>>()
+    };
+
+    if input_animation.reverse {
+        frames.reverse();
+    }
+
+    let frame_count = frames.len();
+
+    // All the import bones with all frames of animation global transforms.
+    let mut imported_bone_animation_transforms: Vec<Vec<Matrix4>> = Vec::with_capacity(imported_file.skeleton.len());
+    for (import_bone_index, import_bone) in imported_file.skeleton.values().enumerate() {
+        let location_channel = match imported_animation.channels.get(&import_bone_index) {
+            Some(import_channel) => bake_channel_keyframes(
+                &import_channel.location,
+                &import_channel.location_tangents,
+                source_frame_count,
+                import_bone.location,
+                |from, to, time| from.lerp(to, time),
+                |value| value,
+            ),
+            None => vec![import_bone.location; source_frame_count],
+        };
+        let rotation_channel = match imported_animation.channels.get(&import_bone_index) {
+            Some(import_channel) => bake_channel_keyframes(
+                &import_channel.rotation,
+                &import_channel.rotation_tangents,
+                source_frame_count,
+                import_bone.rotation,
+                |from, to, time| from.slerp(to, time),
+                |value| value.normalize(),
+            ),
+            None => vec![import_bone.rotation; source_frame_count],
+        };
+
+        let mut imported_animation_transform = Vec::with_capacity(frame_count);
+        for (frame, &source_frame) in frames.iter().enumerate() {
+            let location = location_channel[source_frame];
+            let rotation = rotation_channel[source_frame];
+            let transform = Matrix4::from_rotation_translation(rotation, location);
+
+            if let Some(parent_transform) = import_bone.parent.map(|parent_index| imported_bone_animation_transforms[parent_index][frame]) {
+                imported_animation_transform.push(parent_transform * transform);
+                continue;
+            }
+
+            let space_transform = create_space_transform(imported_file.up, imported_file.forward);
+            imported_animation_transform.push(space_transform.inverse() * transform);
+        }
+        imported_bone_animation_transforms.push(imported_animation_transform);
+    }
+
+    // All the import bones rest pose global transforms, used to correct the difference to the processed rest pose.
+    let mut imported_bone_rest_transforms: Vec<Matrix4> = Vec::with_capacity(imported_file.skeleton.len());
+    for import_bone in imported_file.skeleton.values() {
+        let transform = Matrix4::from_rotation_translation(import_bone.rotation, import_bone.location);
+        match import_bone.parent {
+            Some(parent_index) => imported_bone_rest_transforms.push(imported_bone_rest_transforms[parent_index] * transform),
+            None => imported_bone_rest_transforms.push(create_space_transform(imported_file.up, imported_file.forward).inverse() * transform),
+        }
+    }
+
+    let renamed_import_bones = imported_file
+        .skeleton
+        .keys()
+        .map(|import_bone_name| processed_bone_data.bone_renames.rename(import_bone_name))
+        .collect::<Vec<_>>();
+
+    // All the proceed bones with all frames of animation global transforms using import animation.
+    let mut processed_bone_animation_transforms: Vec<Vec<Matrix4>> = Vec::with_capacity(processed_bone_data.processed_bones.len());
+    let mut unmatched_bones = Vec::new();
+    for (processed_bone_name, processed_bone) in &processed_bone_data.processed_bones {
+        // Mapped bones use the source bone name, else the renamed source bone is matched.
+        let import_bone_index = match input_animation
+            .bone_mappings
+            .iter()
+            .find(|bone_mapping| bone_mapping.target_bone == *processed_bone_name)
+        {
+            Some(bone_mapping) => imported_file.skeleton.get_index_of(&bone_mapping.source_bone),
+            None => renamed_import_bones.iter().position(|renamed_bone| renamed_bone == processed_bone_name),
+        };
+
+        if let Some(import_bone_index) = import_bone_index {
+            let imported_animation_transform = &imported_bone_animation_transforms[import_bone_index];
+            if !input_animation.retarget_rest_pose {
+                processed_bone_animation_transforms.push(imported_animation_transform.clone());
+                continue;
+            }
+
+            // The rotation keeps the offset between the rest poses, the location keeps the processed bone length.
+            let (_, source_rest_rotation, source_rest_location) = imported_bone_rest_transforms[import_bone_index].to_scale_rotation_translation();
+            let (_, target_rest_rotation, target_rest_location) = processed_bone.world_transform.to_scale_rotation_translation();
+            let rotation_correction = source_rest_rotation.inverse() * target_rest_rotation;
+            let processed_parent_bone_animation_transform = processed_bone.parent.map(|parent_index| &processed_bone_animation_transforms[parent_index]);
+
+            let retargeted_transform = imported_animation_transform
+                .iter()
+                .enumerate()
+                .map(|(frame, transform)| {
+                    let (_, rotation, location) = transform.to_scale_rotation_translation();
+                    let location = match processed_parent_bone_animation_transform {
+                        Some(parent_transform) => parent_transform[frame].transform_point3(processed_bone.location),
+                        None => location - source_rest_location + target_rest_location,
+                    };
+                    Matrix4::from_rotation_translation(rotation * rotation_correction, location)
+                })
+                .collect();
+            processed_bone_animation_transforms.push(retargeted_transform);
+            continue;
+        }
+
+        if processed_bone_name != super::bones::STATIC_PROP_BONE_NAME {
+            unmatched_bones.push(processed_bone_name.as_str());
+        }
+
+        if let Some(processed_parent_bone_animation_transform) = processed_bone
+            .parent
+            .map(|processed_bone_parent_index| &processed_bone_animation_transforms[processed_bone_parent_index])
+        {
+            processed_bone_animation_transforms.push(
+                processed_parent_bone_animation_transform
+                    .iter()
+                    .map(|parent_transform| parent_transform * Matrix4::from_rotation_translation(processed_bone.rotation, processed_bone.location))
+                    .collect(),
+            );
+            continue;
+        }
+
+        processed_bone_animation_transforms.push(vec![processed_bone.world_transform; frame_count]);
+    }
+
+    if !unmatched_bones.is_empty() {
+        warn!(
+            "Animation \"{}\" Has No Source Bone For \"{}\", Holding The Rest Pose!",
+            input_animation.name,
+            unmatched_bones.join("\", \"")
+        );
+    }
+
+    // TODO: Implement animation processing.
+    // TODO: Add a check if the position data is going to be out of bounds.
+
+    // All the proceed bones with all frames of animation local transforms.
+    let mut processed_bone_animation_local_transforms: Vec<Vec<Matrix4>> = Vec::with_capacity(processed_bone_data.processed_bones.len());
+    for (processed_bone_index, processed_bone) in processed_bone_data.processed_bones.values().enumerate() {
+        let mut processed_bone_animation_local_transform = Vec::with_capacity(frame_count);
+        let processed_bone_animation_transform = &processed_bone_animation_transforms[processed_bone_index];
+        if let Some(processed_parent_bone_animation_transform) = processed_bone
+            .parent
+            .map(|processed_bone_parent_index| &processed_bone_animation_transforms[processed_bone_parent_index])
+        {
+            for frame in 0..frame_count {
+                let parent_transform = processed_parent_bone_animation_transform[frame];
+                let transform = processed_bone_animation_transform[frame];
+                processed_bone_animation_local_transform.push(parent_transform.inverse() * transform);
+            }
+            processed_bone_animation_local_transforms.push(processed_bone_animation_local_transform);
+            continue;
+        }
+        processed_bone_animation_local_transforms.push(processed_bone_animation_transform.clone());
+    }
+
+    Ok(processed_bone_animation_local_transforms)
+}
+
+/// Convert channel keyframes to a continuous set of values.
+///
+/// Frames between keys are interpolated with the tangents if both keys have them, else with the interpolate function.
+/// Frames outside of the keys hold the first or last key.
+fn bake_channel_keyframes<T>(
+    channel: &IndexMap<usize, T>,
+    tangents: &IndexMap<usize, import::Tangents<T>>,
+    frame_count: usize,
+    default: T,
+    interpolate: impl Fn(T, T, f64) -> T,
+    normalize: impl Fn(T) -> T,
+) -> Vec<T>
+where
+    T: Copy + Add<Output = T> + Mul<f64, Output = T>,
+{
+    let mut keys = channel.keys().copied().collect::<Vec<_>>();
+    keys.sort_unstable();
+
+    if keys.is_empty() {
+        return vec![default; frame_count];
+    }
+
+    let mut baked_channel = Vec::with_capacity(frame_count);
+    for frame in 0..frame_count {
+        let next_key_index = keys.partition_point(|&key| key < frame);
+
+        if next_key_index == 0 {
+            baked_channel.push(channel[&keys[0]]);
+            continue;
+        }
+
+        if next_key_index == keys.len() {
+            baked_channel.push(channel[&keys[keys.len() - 1]]);
+            continue;
+        }
+
+        let next_key = keys[next_key_index];
+        if next_key == frame {
+            baked_channel.push(channel[&next_key]);
+            continue;
+        }
+
+        let previous_key = keys[next_key_index - 1];
+        let span = (next_key - previous_key) as f64;
+        let time = (frame - previous_key) as f64 / span;
+        let from = channel[&previous_key];
+        let to = channel[&next_key];
+
+        if let (Some(from_tangents), Some(to_tangents)) = (tangents.get(&previous_key), tangents.get(&next_key)) {
+            let time_squared = time * time;
+            let time_cubed = time_squared * time;
+            let from_weight = 2.0 * time_cubed - 3.0 * time_squared + 1.0;
+            let from_tangent_weight = time_cubed - 2.0 * time_squared + time;
+            let to_weight = -2.0 * time_cubed + 3.0 * time_squared;
+            let to_tangent_weight = time_cubed - time_squared;
+
+            let value =
+                from * from_weight + from_tangents.outgoing * (from_tangent_weight * span) + to * to_weight + to_tangents.incoming * (to_tangent_weight * span);
+            baked_channel.push(normalize(value));
+            continue;
+        }
+
+        baked_channel.push(interpolate(from, to, time));
+    }
+
+    baked_channel
+}
+
+/// Removes the motion from the root bones and creates the movement blocks for the extracted motion.
+fn extract_motion(
+    input_animation: &input::Animation,
+    frame_rate: f32,
+    processed_bone_data: &super::BoneData,
+    local_transforms: &mut [Vec<Matrix4>],
+) -> Vec<super::Movement> {
+    let mut motion_flags = super::MovementFlags::empty();
+    motion_flags.set(super::MovementFlags::LX, input_animation.motion_x);
+    motion_flags.set(super::MovementFlags::LY, input_animation.motion_y);
+    motion_flags.set(super::MovementFlags::LZ, input_animation.motion_z);
+    motion_flags.set(super::MovementFlags::LXR, input_animation.motion_x_rotation);
+    motion_flags.set(super::MovementFlags::LYR, input_animation.motion_y_rotation);
+    motion_flags.set(super::MovementFlags::LZR, input_animation.motion_z_rotation);
+
+    let frame_count = local_transforms[0].len();
+    if motion_flags.is_empty() || frame_count < 2 {
+        return Vec::new();
+    }
+
+    if !input_animation.motion_piecewise {
+        motion_flags.insert(super::MovementFlags::LINEAR);
+    }
+
+    let Some(root_bone_index) = processed_bone_data.processed_bones.values().position(|bone| bone.parent.is_none()) else {
+        return Vec::new();
+    };
+
+    let location_mask = Vector3::new(
+        input_animation.motion_x as u8 as f64,
+        input_animation.motion_y as u8 as f64,
+        input_animation.motion_z as u8 as f64,
+    );
+    let rotation_mask = Vector3::new(
+        input_animation.motion_x_rotation as u8 as f64,
+        input_animation.motion_y_rotation as u8 as f64,
+        input_animation.motion_z_rotation as u8 as f64,
+    );
+
+    // The root location and rotation relative to the first frame, with the rotation unwrapped to be continuous.
+    let root_transforms = &local_transforms[root_bone_index];
+    let start_location = root_transforms[0].translation;
+    let mut root_locations = Vec::with_capacity(frame_count);
+    let mut root_angles: Vec<Vector3> = Vec::with_capacity(frame_count);
+    let mut previous_angles = Vector3::from(Quaternion::from_affine3(&root_transforms[0]).to_euler(EULER_ROTATION));
+    for root_transform in root_transforms {
+        root_locations.push((root_transform.translation - start_location) * location_mask);
+
+        let angles = Vector3::from(Quaternion::from_affine3(root_transform).to_euler(EULER_ROTATION));
+        let mut angle_delta = angles - previous_angles;
         for axis in 0..3 {
-            position[axis] /= i16::MAX as f64;
-            rotation[axis] /= i16::MAX as f64;
+            angle_delta[axis] = (angle_delta[axis] + PI).rem_euclid(TAU) - PI;
         }
+        previous_angles = angles;
+
+        let last_angles = root_angles.last().copied().unwrap_or_default();
+        root_angles.push(last_angles + angle_delta);
+    }
+    for angles in &mut root_angles {
+        *angles *= rotation_mask;
     }
 
-    Ok(super::AnimationData {
-        processed_animations,
-        animation_scales,
-        remapped_animations,
-    })
+    let last_frame = frame_count - 1;
+    let segment_frames = if input_animation.motion_piecewise {
+        input_animation.motion_segment_frames.clamp(1, last_frame)
+    } else {
+        last_frame
+    };
+
+    let mut segment_ends = (segment_frames..last_frame).step_by(segment_frames).collect::<Vec<_>>();
+    segment_ends.push(last_frame);
+
+    let mut frame_motions = vec![(Vector3::ZERO, Vector3::ZERO); frame_count];
+    let mut movements = Vec::with_capacity(segment_ends.len());
+    let mut segment_start = 0;
+    for segment_end in segment_ends {
+        let segment_length = (segment_end - segment_start) as f64;
+        let segment_start_location = root_locations[segment_start];
+        let segment_end_location = root_locations[segment_end];
+        let segment_start_angles = root_angles[segment_start];
+        let segment_end_angles = root_angles[segment_end];
+
+        for (frame, frame_motion) in frame_motions.iter_mut().enumerate().take(segment_end + 1).skip(segment_start) {
+            let time = (frame - segment_start) as f64 / segment_length;
+            *frame_motion = (
+                segment_start_location.lerp(segment_end_location, time),
+                segment_start_angles.lerp(segment_end_angles, time),
+            );
+        }
+
+        let segment_vector = segment_end_location - segment_start_location;
+        let segment_duration = segment_length / frame_rate as f64;
+        movements.push(super::Movement {
+            end_frame: segment_end,
+            flags: motion_flags,
+            velocity: segment_vector.length() / segment_duration,
+            angle: segment_end_angles.z.to_degrees(),
+            vector: Quaternion::from_rotation_z(-segment_start_angles.z) * segment_vector,
+            position: segment_end_location,
+        });
+
+        segment_start = segment_end;
+    }
+
+    // All root bones are moved by the extracted motion to keep them relative to each other.
+    for (bone_index, bone) in processed_bone_data.processed_bones.values().enumerate() {
+        if bone.parent.is_some() {
+            continue;
+        }
+
+        for (local_transform, (location, angles)) in local_transforms[bone_index].iter_mut().zip(&frame_motions) {
+            let motion_transform = Matrix4::from_rotation_translation(Quaternion::from_euler(EULER_ROTATION, angles.x, angles.y, angles.z), *location);
+            *local_transform = motion_transform.inverse() * *local_transform;
+        }
+    }
+
+    debug!("Extracted {} movement blocks from \"{}\"", movements.len(), input_animation.name);
+
+    movements
 }
@@ -1,5 +1,6 @@
 use indexmap::IndexMap;
 use rayon::iter::{IntoParallelRefIterator, ParallelIterator};
+use regex::Regex;
 use std::path::PathBuf;
 use thiserror::Error as ThisError;
 
@@ -29,47 +30,128 @@ pub enum ProcessingBoneError {
     MissingIkBone(String, String),
     #[error("Ik Chain Bone \"{0}\" Must Have A Parent")]
     IkBoneIsRoot(String),
+    #[error("Static Prop Animation \"{0}\" Has More Than One Frame")]
+    StaticPropAnimated(String),
+    #[error("Bone Rename Pattern \"{0}\" Is Not A Valid Regex")]
+    InvalidRenameRegex(String),
+    #[error("Bone Hierarchy Has A Loop \"{0}\"")]
+    CircularHierarchy(String),
+    #[error("Bone \"{0}\" Can Not Be Kept And Force Collapsed")]
+    KeepAndCollapse(String),
+    #[error("Jiggle Bone \"{0}\" Length Must Be Greater Than Zero")]
+    InvalidJiggleLength(String),
+    #[error("Jiggle Bone \"{0}\" Has A Minimum Constraint Larger Than The Maximum")]
+    InvalidJiggleConstraint(String),
+    #[error("Bone \"{0}\" Can Not Have More Than One Procedural")]
+    MultipleProcedurals(String),
+    #[error("Procedural Bone \"{0}\" Has No Triggers")]
+    NoProceduralTriggers(String),
+    #[error("Procedural Bone \"{0}\" Has A Trigger Tolerance That Is Not Greater Than Zero")]
+    InvalidTriggerTolerance(String),
+    #[error("Procedural Bone \"{0}\" Uses Bone \"{1}\" That Doesn't Exist, Set Keep Bone If It Was Collapsed")]
+    ProceduralBoneNotFound(String, String),
+    #[error("Bone Controller Bone \"{0}\" Doesn't Exist, Set Keep Bone If It Was Collapsed")]
+    MissingControllerBone(String),
+    #[error("Bone Controller Index {0} Is Out Of Range, Must Be From 0 To 3")]
+    InvalidControllerIndex(usize),
+    #[error("Bone Controller On Bone \"{0}\" Has The Same Start And End")]
+    EmptyControllerRange(String),
+    #[error("Bone \"{0}\" Has More Than One Controller On The Same Axis")]
+    DuplicateBoneController(String),
 }
 
-pub fn process_bones(input_data: &input::SourceInput, source_files: &FileManager) -> Result<super::BoneData, ProcessingBoneError> {
-    let mut processed_bones = IndexMap::new();
+/// The name of the only bone of a static prop.
+pub const STATIC_PROP_BONE_NAME: &str = "static_prop";
 
-    for input_model_group in &input_data.model_groups {
-        for input_model in &input_model_group.models {
-            if input_model.blank {
-                continue;
-            }
+/// The compiled rules to rename the bones of the source files.
+#[derive(Debug, Default)]
+pub struct BoneRenames {
+    rules: Vec<(input::BoneRename, Option<Regex>)>,
+}
 
-            load_bones_from_model_source(input_model, &input_model_group.name, source_files, &mut processed_bones)?;
+impl BoneRenames {
+    fn new(bone_renames: &[input::BoneRename]) -> Result<Self, ProcessingBoneError> {
+        let mut rules = Vec::with_capacity(bone_renames.len());
+        for bone_rename in bone_renames {
+            let regex = match bone_rename.kind {
+                input::BoneRenameKind::Regex => {
+                    Some(Regex::new(&bone_rename.pattern).map_err(|_| ProcessingBoneError::InvalidRenameRegex(bone_rename.pattern.clone()))?)
+                }
+                _ => None,
+            };
+            rules.push((bone_rename.clone(), regex));
         }
+
+        Ok(Self { rules })
     }
 
-    for input_animation in &input_data.animations {
-        load_bones_from_animation_source(input_animation, source_files, &mut processed_bones)?;
+    /// Returns the name of a source bone after every rule is applied in order.
+    pub fn rename(&self, bone_name: &str) -> String {
+        let mut renamed_bone = String::from(bone_name);
+        for (rule, regex) in &self.rules {
+            renamed_bone = match rule.kind {
+                input::BoneRenameKind::Exact if renamed_bone == rule.pattern => rule.replacement.clone(),
+                input::BoneRenameKind::Exact => renamed_bone,
+                input::BoneRenameKind::Prefix => match renamed_bone.strip_prefix(&rule.pattern) {
+                    Some(remaining) => format!("{}{remaining}", rule.replacement),
+                    None => renamed_bone,
+                },
+                input::BoneRenameKind::Regex => {
+                    let regex = regex.as_ref().expect("Regex Rule Should Be Compiled");
+                    regex.replace_all(&renamed_bone, rule.replacement.as_str()).into_owned()
+                }
+            };
+        }
+        renamed_bone
     }
+}
+
+pub fn process_bones(input_data: &input::SourceInput, source_files: &FileManager) -> Result<super::BoneData, ProcessingBoneError> {
+    if input_data.model_properties.static_prop {
+        return create_static_prop_bone(input_data, source_files);
+    }
+
+    let bone_renames = BoneRenames::new(&input_data.bone_renames)?;
+
+    let (mut processed_bones, skeleton_report) = load_source_bones(input_data, source_files, &bone_renames)?;
 
     verbose!("Loaded {} source bones", processed_bones.len());
 
+    let mismatched_bone_count = skeleton_report.mismatched_bones().count();
+    if mismatched_bone_count > 0 {
+        for (bone_name, bone_sources) in skeleton_report.mismatched_bones() {
+            for bone_source in bone_sources.iter().filter(|bone_source| bone_source.mismatched) {
+                warn!(
+                    "Bone \"{}\" In {:?} Differs From {:?}: Parent {:?}, Location Delta {:.4}, Rotation Delta {:.4}°",
+                    bone_name, bone_source.file, bone_sources[0].file, bone_source.parent, bone_source.location_delta, bone_source.rotation_delta
+                );
+            }
+        }
+        warn!("{mismatched_bone_count} Bones Differ Between Source Files, The First File Is Used!");
+    }
+
     add_define_bones(&input_data.bone_properties, &mut processed_bones)?;
 
     enforce_bone_hierarchy(&input_data.bone_properties, &mut processed_bones)?;
 
-    // TODO: Check for circular bone hierarchy.
+    check_circular_hierarchy(&processed_bones)?;
+
+    sort_bone_hierarchy(&input_data.bone_properties, &mut processed_bones);
 
     add_flags_from_property(&input_data.bone_properties, &mut processed_bones)?;
 
+    add_procedural_flags(&input_data.procedural_bones, &mut processed_bones)?;
+
     enforce_bone_transforms(&input_data.bone_properties, &mut processed_bones);
 
     create_bone_world_transform_matrixes(&mut processed_bones); // This can move to enforce bone transform if needed world transforms.
 
-    collapse_unused_bones(&mut processed_bones);
+    collapse_unused_bones(&input_data.bone_properties, &mut processed_bones)?;
 
     if processed_bones.len() > (i8::MAX as usize) + 1 {
         return Err(ProcessingBoneError::TooManyBones);
     }
 
-    // TODO: Enforce the order of the bone properties to the processed bones order.
-
     // Update bones local location and orientation.
     for source_bone_index in 0..processed_bones.len() {
         if let Some(parent_matrix) = processed_bones[source_bone_index]
@@ -88,8 +170,12 @@ pub fn process_bones(input_data: &input::SourceInput, source_files: &FileManager
         bone.location = bone.world_transform.translation;
     }
 
+    create_procedural_bones(&input_data.procedural_bones, &mut processed_bones)?;
+
     let ik_chains = create_ik_chains(&input_data.bone_properties, &processed_bones)?;
 
+    let bone_controllers = create_bone_controllers(&input_data.bone_controllers, &processed_bones)?;
+
     let mut sorted_bones_by_name = (0..processed_bones.len() as u8).collect::<Vec<_>>();
     sorted_bones_by_name.sort_by(|from, to| {
         let bone_from = processed_bones.get_index(*from as usize).unwrap().0;
@@ -101,14 +187,161 @@ pub fn process_bones(input_data: &input::SourceInput, source_files: &FileManager
         processed_bones,
         sorted_bones_by_name,
         ik_chains,
+        bone_controllers,
+        bone_renames,
     })
 }
 
+/// Creates the single root bone of a static prop, all geometry is kept in world space.
+fn create_static_prop_bone(input_data: &input::SourceInput, source_files: &FileManager) -> Result<super::BoneData, ProcessingBoneError> {
+    for input_animation in &input_data.animations {
+        let source_file_path = input_animation
+            .source_file_path
+            .as_ref()
+            .ok_or(ProcessingBoneError::NoAnimationFileSource(input_animation.name.clone()))?;
+
+        let imported_file = source_files
+            .get_file_data(source_file_path)
+            .ok_or(ProcessingBoneError::FileSourceNotLoaded(source_file_path.clone()))?;
+
+        if imported_file
+            .animations
+            .get_index(input_animation.source_animation)
+            .is_some_and(|(_, animation)| animation.frame_count.get() > 1)
+        {
+            return Err(ProcessingBoneError::StaticPropAnimated(input_animation.name.clone()));
+        }
+    }
+
+    if !input_data.bone_properties.is_empty() {
+        warn!("Bone Properties Are Ignored For Static Props!");
+    }
+
+    if !input_data.bone_controllers.is_empty() {
+        warn!("Bone Controllers Are Ignored For Static Props!");
+    }
+
+    verbose!("Collapsed all bones to \"{STATIC_PROP_BONE_NAME}\" for static prop.");
+
+    let mut processed_bones = IndexMap::new();
+    processed_bones.insert(
+        String::from(STATIC_PROP_BONE_NAME),
+        super::Bone {
+            flags: super::BoneFlags::USED_BY_VERTEX | super::BoneFlags::USED_BY_HITBOX,
+            ..Default::default()
+        },
+    );
+
+    Ok(super::BoneData {
+        processed_bones,
+        sorted_bones_by_name: vec![0],
+        ik_chains: IndexMap::new(),
+        bone_controllers: Vec::new(),
+        bone_renames: BoneRenames::default(),
+    })
+}
+
+/// Creates a report of every source bone, comparing the parent and transform to the first file that defines the bone.
+///
+/// Processing the bones creates the same report while loading the bones, this is for showing the report before processing.
+pub fn create_skeleton_report(input_data: &input::SourceInput, source_files: &FileManager) -> Result<super::SkeletonReport, ProcessingBoneError> {
+    let bone_renames = BoneRenames::new(&input_data.bone_renames)?;
+    let (_, skeleton_report) = load_source_bones(input_data, source_files, &bone_renames)?;
+    Ok(skeleton_report)
+}
+
+/// Merges the skeletons of every model and animation source, reporting the bones that differ from the first file that defines them.
+fn load_source_bones(
+    input_data: &input::SourceInput,
+    source_files: &FileManager,
+    bone_renames: &BoneRenames,
+) -> Result<(IndexMap<String, super::Bone>, super::SkeletonReport), ProcessingBoneError> {
+    let mut processed_bones = IndexMap::new();
+    let mut skeleton_report = super::SkeletonReport::default();
+    let tolerances = &input_data.skeleton_tolerances;
+
+    for input_model_group in &input_data.model_groups {
+        for input_model in &input_model_group.models {
+            if input_model.blank {
+                continue;
+            }
+
+            load_bones_from_model_source(
+                input_model,
+                &input_model_group.name,
+                source_files,
+                bone_renames,
+                tolerances,
+                &mut processed_bones,
+                &mut skeleton_report,
+            )?;
+        }
+    }
+
+    for input_animation in &input_data.animations {
+        load_bones_from_animation_source(
+            input_animation,
+            source_files,
+            bone_renames,
+            tolerances,
+            &mut processed_bones,
+            &mut skeleton_report,
+        )?;
+    }
+
+    Ok((processed_bones, skeleton_report))
+}
+
+impl super::SkeletonReport {
+    /// Adds a bone of a source file, comparing it to the bone already loaded from the first file that defines it.
+    fn add_bone_source(
+        &mut self,
+        tolerances: &input::SkeletonTolerances,
+        processed_bones: &IndexMap<String, super::Bone>,
+        source_file_path: &PathBuf,
+        bone_name: &str,
+        parent_name: Option<String>,
+        bone_transform: &Matrix4,
+    ) {
+        let bone_sources = self.bones.entry(String::from(bone_name)).or_default();
+
+        // The same file can be used by more than one source.
+        if bone_sources.iter().any(|bone_source| bone_source.file == *source_file_path) {
+            return;
+        }
+
+        let location = bone_transform.translation;
+        let rotation = Quaternion::from_affine3(bone_transform);
+        let (location_delta, rotation_delta, parent_matches) = match processed_bones.get(bone_name) {
+            Some(first_bone) => {
+                let first_parent = first_bone.parent.map(|parent_index| processed_bones.get_index(parent_index).unwrap().0);
+                (
+                    location.distance(first_bone.location),
+                    rotation.angle_between(first_bone.rotation).to_degrees(),
+                    parent_name.as_ref() == first_parent,
+                )
+            }
+            None => (0.0, 0.0, true),
+        };
+
+        bone_sources.push(super::BoneSource {
+            file: source_file_path.clone(),
+            parent: parent_name,
+            location_delta,
+            rotation_delta,
+            mismatched: !parent_matches || location_delta > tolerances.location || rotation_delta > tolerances.rotation,
+        });
+    }
+}
+
 fn load_bones_from_model_source(
     model: &input::Model,
     model_group_name: &str,
     source_files: &FileManager,
+    bone_renames: &BoneRenames,
+    tolerances: &input::SkeletonTolerances,
     processed_bones: &mut IndexMap<String, super::Bone>,
+    skeleton_report: &mut super::SkeletonReport,
 ) -> Result<(), ProcessingBoneError> {
     let source_file_path = model
         .source_file_path
@@ -132,29 +365,32 @@ fn load_bones_from_model_source(
             }
         }
 
-        // TODO: Add warns if the bone transform and parent is different from the already loaded bones.
-
-        if let Some(global_bone) = processed_bones.get_mut(import_bone_name) {
-            global_bone.flags.insert(bone_flags);
-            continue;
-        }
-
-        let parent_index = import_bone.parent.map(|index| {
+        let bone_name = bone_renames.rename(import_bone_name);
+        let parent_name = import_bone.parent.map(|index| {
             let (parent_name, _) = imported_file.skeleton.get_index(index).expect("Source Bone Parent Index Should Be Valid");
-            processed_bones.get_index_of(parent_name).expect("Parent Bone Should Already Be Loaded")
+            bone_renames.rename(parent_name)
         });
 
         let source_transform = create_space_transform(imported_file.up, imported_file.forward);
         let bone_matrix = Matrix4::from_rotation_translation(import_bone.rotation, import_bone.location);
-        let bone_transform = if parent_index.is_none() {
+        let bone_transform = if parent_name.is_none() {
             source_transform.inverse() * bone_matrix
         } else {
             bone_matrix
         };
 
-        debug!("Loaded bone \"{import_bone_name}\" from {source_file_path:?}");
+        skeleton_report.add_bone_source(tolerances, processed_bones, source_file_path, &bone_name, parent_name.clone(), &bone_transform);
+
+        if let Some(global_bone) = processed_bones.get_mut(&bone_name) {
+            global_bone.flags.insert(bone_flags);
+            continue;
+        }
+
+        let parent_index = parent_name.map(|parent_name| processed_bones.get_index_of(&parent_name).expect("Parent Bone Should Already Be Loaded"));
+
+        debug!("Loaded bone \"{bone_name}\" from {source_file_path:?}");
         processed_bones.insert(
-            import_bone_name.clone(),
+            bone_name,
             super::Bone {
                 parent: parent_index,
                 location: bone_transform.translation,
@@ -171,7 +407,10 @@ fn load_bones_from_model_source(
 fn load_bones_from_animation_source(
     animation: &input::Animation,
     source_files: &FileManager,
+    bone_renames: &BoneRenames,
+    tolerances: &input::SkeletonTolerances,
     processed_bones: &mut IndexMap<String, super::Bone>,
+    skeleton_report: &mut super::SkeletonReport,
 ) -> Result<(), ProcessingBoneError> {
     let source_file_path = animation
         .source_file_path
@@ -187,27 +426,32 @@ fn load_bones_from_animation_source(
 
         // TODO: Add flags for animated bones.
 
-        if let Some(global_bone) = processed_bones.get_mut(import_bone_name) {
-            global_bone.flags.insert(bone_flags);
-            continue;
-        }
-
-        let parent_index = import_bone.parent.map(|index| {
+        let bone_name = bone_renames.rename(import_bone_name);
+        let parent_name = import_bone.parent.map(|index| {
             let (parent_name, _) = imported_file.skeleton.get_index(index).expect("Source Bone Parent Index Should Be Valid");
-            processed_bones.get_index_of(parent_name).expect("Parent Bone Should Already Be Loaded")
+            bone_renames.rename(parent_name)
         });
 
         let source_transform = create_space_transform(imported_file.up, imported_file.forward);
         let bone_matrix = Matrix4::from_rotation_translation(import_bone.rotation, import_bone.location);
-        let bone_transform = if parent_index.is_none() {
+        let bone_transform = if parent_name.is_none() {
             source_transform.inverse() * bone_matrix
         } else {
             bone_matrix
         };
 
-        debug!("Loaded bone \"{import_bone_name}\" from {source_file_path:?}");
+        skeleton_report.add_bone_source(tolerances, processed_bones, source_file_path, &bone_name, parent_name.clone(), &bone_transform);
+
+        if let Some(global_bone) = processed_bones.get_mut(&bone_name) {
+            global_bone.flags.insert(bone_flags);
+            continue;
+        }
+
+        let parent_index = parent_name.map(|parent_name| processed_bones.get_index_of(&parent_name).expect("Parent Bone Should Already Be Loaded"));
+
+        debug!("Loaded bone \"{bone_name}\" from {source_file_path:?}");
         processed_bones.insert(
-            import_bone_name.clone(),
+            bone_name,
             super::Bone {
                 parent: parent_index,
                 location: bone_transform.translation,
@@ -313,6 +557,83 @@ fn enforce_bone_hierarchy(bone_properties: &[input::BoneProperty], processed_bon
     Ok(())
 }
 
+fn check_circular_hierarchy(processed_bones: &IndexMap<String, super::Bone>) -> Result<(), ProcessingBoneError> {
+    // The bones already known to lead to a root bone.
+    let mut rooted_bones = vec![false; processed_bones.len()];
+
+    for bone_index in 0..processed_bones.len() {
+        let mut bone_path = Vec::new();
+        let mut current_bone_index = Some(bone_index);
+
+        while let Some(path_bone_index) = current_bone_index {
+            if rooted_bones[path_bone_index] {
+                break;
+            }
+
+            if let Some(loop_start) = bone_path.iter().position(|&index| index == path_bone_index) {
+                let mut loop_names = bone_path[loop_start..]
+                    .iter()
+                    .map(|&index| processed_bones.get_index(index).unwrap().0.as_str())
+                    .collect::<Vec<_>>();
+                loop_names.push(processed_bones.get_index(path_bone_index).unwrap().0);
+                return Err(ProcessingBoneError::CircularHierarchy(loop_names.join(" -> ")));
+            }
+
+            bone_path.push(path_bone_index);
+            current_bone_index = processed_bones[path_bone_index].parent;
+        }
+
+        for path_bone_index in bone_path {
+            rooted_bones[path_bone_index] = true;
+        }
+    }
+
+    Ok(())
+}
+
+/// Sorts the bones so parents are before children, bones with properties are placed in the order of the properties.
+fn sort_bone_hierarchy(bone_properties: &[input::BoneProperty], processed_bones: &mut IndexMap<String, super::Bone>) {
+    let bone_orders = processed_bones
+        .keys()
+        .enumerate()
+        .map(|(bone_index, bone_name)| {
+            let property_index = bone_properties.iter().position(|property| property.name == *bone_name);
+            (property_index.unwrap_or(usize::MAX), bone_index)
+        })
+        .collect::<Vec<_>>();
+
+    let mut sorted_bones = Vec::with_capacity(processed_bones.len());
+    let mut placed_bones = vec![false; processed_bones.len()];
+    while sorted_bones.len() < processed_bones.len() {
+        let next_bone_index = (0..processed_bones.len())
+            .filter(|&bone_index| !placed_bones[bone_index])
+            .filter(|&bone_index| processed_bones[bone_index].parent.is_none_or(|parent_index| placed_bones[parent_index]))
+            .min_by_key(|&bone_index| bone_orders[bone_index])
+            .expect("Bone Hierarchy Should Not Have Loops");
+
+        placed_bones[next_bone_index] = true;
+        sorted_bones.push(next_bone_index);
+    }
+
+    if sorted_bones.iter().enumerate().all(|(sorted_index, &bone_index)| sorted_index == bone_index) {
+        return;
+    }
+
+    let mut remapped_indices = vec![0; processed_bones.len()];
+    for (sorted_index, &bone_index) in sorted_bones.iter().enumerate() {
+        remapped_indices[bone_index] = sorted_index;
+    }
+
+    let mut unsorted_bones = std::mem::take(processed_bones).into_iter().map(Some).collect::<Vec<_>>();
+    for bone_index in sorted_bones {
+        let (bone_name, mut bone) = unsorted_bones[bone_index].take().expect("Bone Should Only Be Sorted Once");
+        bone.parent = bone.parent.map(|parent_index| remapped_indices[parent_index]);
+        processed_bones.insert(bone_name, bone);
+    }
+
+    debug!("Sorted bone hierarchy.");
+}
+
 fn add_flags_from_property(bone_properties: &[input::BoneProperty], processed_bones: &mut IndexMap<String, super::Bone>) -> Result<(), ProcessingBoneError> {
     for property in bone_properties {
         let process_bone = match processed_bones.get_mut(&property.name) {
@@ -323,6 +644,11 @@ fn add_flags_from_property(bone_properties: &[input::BoneProperty], processed_bo
             }
         };
 
+        if property.jiggle_bone {
+            process_bone.procedural = Some(super::ProceduralBone::Jiggle(create_jiggle_bone(property)?));
+            process_bone.flags.insert(super::BoneFlags::ALWAYS_PROCEDURAL);
+        }
+
         if property.ik_chain {
             process_bone.flags.insert(super::BoneFlags::USED_BY_ATTACHMENT);
 
@@ -350,6 +676,145 @@ fn add_flags_from_property(bone_properties: &[input::BoneProperty], processed_bo
     Ok(())
 }
 
+fn create_jiggle_bone(property: &input::BoneProperty) -> Result<super::JiggleBone, ProcessingBoneError> {
+    let jiggle = &property.jiggle;
+
+    if jiggle.length <= 0.0 {
+        return Err(ProcessingBoneError::InvalidJiggleLength(property.name.clone()));
+    }
+
+    if (jiggle.define_yaw_constraint && jiggle.minimum_yaw > jiggle.maximum_yaw)
+        || (jiggle.define_pitch_constraint && jiggle.minimum_pitch > jiggle.maximum_pitch)
+        || (jiggle.base_spring && jiggle.base_minimum.cmpgt(jiggle.base_maximum).any())
+    {
+        return Err(ProcessingBoneError::InvalidJiggleConstraint(property.name.clone()));
+    }
+
+    let mut flags = if jiggle.rigid {
+        super::JiggleFlags::RIGID
+    } else {
+        super::JiggleFlags::FLEXIBLE
+    };
+    flags.set(super::JiggleFlags::LENGTH_CONSTRAINT, !jiggle.allow_length_flex);
+    flags.set(super::JiggleFlags::ANGLE_CONSTRAINT, jiggle.define_angle_limit);
+    flags.set(super::JiggleFlags::YAW_CONSTRAINT, jiggle.define_yaw_constraint);
+    flags.set(super::JiggleFlags::PITCH_CONSTRAINT, jiggle.define_pitch_constraint);
+    flags.set(super::JiggleFlags::BASE_SPRING, jiggle.base_spring);
+
+    Ok(super::JiggleBone {
+        flags,
+        length: jiggle.length,
+        tip_mass: jiggle.tip_mass,
+        yaw_stiffness: jiggle.yaw_stiffness,
+        yaw_damping: jiggle.yaw_damping,
+        pitch_stiffness: jiggle.pitch_stiffness,
+        pitch_damping: jiggle.pitch_damping,
+        along_stiffness: jiggle.along_stiffness,
+        along_damping: jiggle.along_damping,
+        angle_limit: jiggle.angle_limit.to_radians(),
+        minimum_yaw: jiggle.minimum_yaw.to_radians(),
+        maximum_yaw: jiggle.maximum_yaw.to_radians(),
+        yaw_friction: jiggle.yaw_friction,
+        yaw_bounce: jiggle.yaw_bounce,
+        minimum_pitch: jiggle.minimum_pitch.to_radians(),
+        maximum_pitch: jiggle.maximum_pitch.to_radians(),
+        pitch_friction: jiggle.pitch_friction,
+        pitch_bounce: jiggle.pitch_bounce,
+        base_mass: jiggle.base_mass,
+        base_stiffness: jiggle.base_stiffness,
+        base_damping: jiggle.base_damping,
+        base_minimum: jiggle.base_minimum,
+        base_maximum: jiggle.base_maximum,
+        base_friction: jiggle.base_friction,
+    })
+}
+
+/// Marks every bone driven by a procedural bone so it is not collapsed.
+fn add_procedural_flags(procedural_bones: &[input::ProceduralBone], processed_bones: &mut IndexMap<String, super::Bone>) -> Result<(), ProcessingBoneError> {
+    for procedural_bone in procedural_bones {
+        let bone_name = procedural_bone.bone();
+        let Some(process_bone) = processed_bones.get_mut(bone_name) else {
+            warn!("Procedural bone \"{bone_name}\" does not exist to drive it");
+            continue;
+        };
+
+        if process_bone.procedural.is_some() || process_bone.flags.contains(super::BoneFlags::ALWAYS_PROCEDURAL) {
+            return Err(ProcessingBoneError::MultipleProcedurals(String::from(bone_name)));
+        }
+
+        process_bone.flags.insert(super::BoneFlags::ALWAYS_PROCEDURAL);
+    }
+
+    Ok(())
+}
+
+/// Creates the procedural data of the driven bones, this must be after the bones are collapsed so the indices are final.
+fn create_procedural_bones(procedural_bones: &[input::ProceduralBone], processed_bones: &mut IndexMap<String, super::Bone>) -> Result<(), ProcessingBoneError> {
+    for procedural_bone in procedural_bones {
+        let bone_name = procedural_bone.bone();
+        let Some(bone_index) = processed_bones.get_index_of(bone_name) else {
+            continue;
+        };
+
+        let find_bone = |name: &str| {
+            processed_bones
+                .get_index_of(name)
+                .ok_or_else(|| ProcessingBoneError::ProceduralBoneNotFound(String::from(bone_name), String::from(name)))
+        };
+
+        let (parent_name, procedural) = match procedural_bone {
+            input::ProceduralBone::Quaternion(quaternion_bone) => {
+                if quaternion_bone.triggers.is_empty() {
+                    return Err(ProcessingBoneError::NoProceduralTriggers(String::from(bone_name)));
+                }
+
+                let mut triggers = Vec::with_capacity(quaternion_bone.triggers.len());
+                for trigger in &quaternion_bone.triggers {
+                    if trigger.tolerance <= 0.0 {
+                        return Err(ProcessingBoneError::InvalidTriggerTolerance(String::from(bone_name)));
+                    }
+
+                    let trigger_rotation = trigger.trigger.map(f64::to_radians);
+                    let rotation = trigger.rotation.map(f64::to_radians);
+                    triggers.push(super::QuaternionTrigger {
+                        inverse_tolerance: 1.0 / trigger.tolerance.to_radians(),
+                        trigger: Quaternion::from_euler(EULER_ROTATION, trigger_rotation.x, trigger_rotation.y, trigger_rotation.z),
+                        position: quaternion_bone.base_position + trigger.position,
+                        rotation: Quaternion::from_euler(EULER_ROTATION, rotation.x, rotation.y, rotation.z),
+                    });
+                }
+
+                (
+                    &quaternion_bone.parent,
+                    super::ProceduralBone::Quaternion(super::QuaternionBone {
+                        control: find_bone(&quaternion_bone.control)?,
+                        triggers,
+                    }),
+                )
+            }
+            input::ProceduralBone::AimAt(aim_at_bone) => (
+                &aim_at_bone.parent,
+                super::ProceduralBone::AimAt(super::AimAtBone {
+                    parent: find_bone(&aim_at_bone.parent)?,
+                    aim: find_bone(&aim_at_bone.aim)?,
+                    aim_vector: aim_at_bone.aim_vector,
+                    up_vector: aim_at_bone.up_vector,
+                    base_position: aim_at_bone.base_position,
+                }),
+            ),
+        };
+
+        let parent_index = find_bone(parent_name)?;
+        if processed_bones[bone_index].parent != Some(parent_index) {
+            warn!("Procedural bone \"{bone_name}\" is not a child of \"{parent_name}\", the bone may be driven incorrectly");
+        }
+
+        processed_bones[bone_index].procedural = Some(procedural);
+    }
+
+    Ok(())
+}
+
 fn create_bone_world_transform_matrixes(processed_bones: &mut IndexMap<String, super::Bone>) {
     for source_bone_index in 0..processed_bones.len() {
         if let Some(parent_matrix) = processed_bones[source_bone_index]
@@ -401,18 +866,48 @@ fn enforce_bone_transforms(bone_properties: &[input::BoneProperty], processed_bo
     }
 }
 
-fn collapse_unused_bones(processed_bones: &mut IndexMap<String, super::Bone>) {
+fn collapse_unused_bones(bone_properties: &[input::BoneProperty], processed_bones: &mut IndexMap<String, super::Bone>) -> Result<(), ProcessingBoneError> {
+    if let Some(property) = bone_properties.iter().find(|property| property.keep_bone && property.force_collapse) {
+        return Err(ProcessingBoneError::KeepAndCollapse(property.name.clone()));
+    }
+
+    // Anything using a forced collapsed bone is moved to the parent, so the parent is used by it as well.
+    for bone_index in (0..processed_bones.len()).rev() {
+        let (bone_name, bone) = processed_bones.get_index(bone_index).expect("Bone Index Should Be Valid");
+        if !bone_properties.iter().any(|property| property.force_collapse && property.name == *bone_name) {
+            continue;
+        }
+
+        if let Some(parent_index) = bone.parent {
+            let bone_flags = bone.flags;
+            processed_bones[parent_index].flags.insert(bone_flags);
+        }
+    }
+
+    let mut collapsed_bones = Vec::new();
     let mut current_bone_index = 0;
-    let mut collapse_count = 0;
     while current_bone_index < processed_bones.len() {
         let (current_bone_name, current_bone) = processed_bones.get_index(current_bone_index).expect("Current Bone Index Should Be Valid");
-        if !current_bone.flags.is_empty() {
-            current_bone_index += 1;
-            continue;
-        }
+        let property = bone_properties.iter().find(|property| property.name == *current_bone_name);
+
+        let collapse_reason = match property {
+            Some(property) if property.force_collapse => "Forced By Bone Property",
+            Some(property) if property.keep_bone => {
+                if current_bone.flags.is_empty() {
+                    verbose!("Kept unused bone \"{current_bone_name}\" by bone property.");
+                }
+                current_bone_index += 1;
+                continue;
+            }
+            _ if current_bone.flags.is_empty() => "Unused",
+            _ => {
+                current_bone_index += 1;
+                continue;
+            }
+        };
 
-        collapse_count += 1;
         debug!("Collapsing \"{current_bone_name}\"!");
+        collapsed_bones.push((current_bone_name.clone(), collapse_reason));
 
         let current_bone_parent = current_bone.parent;
         processed_bones.shift_remove_index(current_bone_index);
@@ -431,7 +926,72 @@ fn collapse_unused_bones(processed_bones: &mut IndexMap<String, super::Bone>) {
             }
         }
     }
-    verbose!("Collapsed {collapse_count} bones.");
+
+    for (collapsed_bone_name, collapse_reason) in &collapsed_bones {
+        verbose!("Collapsed \"{collapsed_bone_name}\": {collapse_reason}.");
+    }
+    verbose!("Collapsed {} bones.", collapsed_bones.len());
+
+    Ok(())
+}
+
+fn create_bone_controllers(
+    input_bone_controllers: &[input::BoneController],
+    bones: &IndexMap<String, super::Bone>,
+) -> Result<Vec<super::BoneController>, ProcessingBoneError> {
+    let mut bone_controllers: Vec<super::BoneController> = Vec::with_capacity(input_bone_controllers.len());
+
+    for input_bone_controller in input_bone_controllers {
+        let bone = bones
+            .get_index_of(&input_bone_controller.bone)
+            .ok_or_else(|| ProcessingBoneError::MissingControllerBone(input_bone_controller.bone.clone()))?;
+
+        if input_bone_controller.index > 3 {
+            return Err(ProcessingBoneError::InvalidControllerIndex(input_bone_controller.index));
+        }
+
+        let range = input_bone_controller.end - input_bone_controller.start;
+        if range == 0.0 {
+            return Err(ProcessingBoneError::EmptyControllerRange(input_bone_controller.bone.clone()));
+        }
+
+        let mut flags = match input_bone_controller.axis {
+            input::BoneControllerAxis::X => super::BoneControllerFlags::X,
+            input::BoneControllerAxis::Y => super::BoneControllerFlags::Y,
+            input::BoneControllerAxis::Z => super::BoneControllerFlags::Z,
+            input::BoneControllerAxis::XR => super::BoneControllerFlags::XR,
+            input::BoneControllerAxis::YR => super::BoneControllerFlags::YR,
+            input::BoneControllerAxis::ZR => super::BoneControllerFlags::ZR,
+        };
+
+        if bone_controllers
+            .iter()
+            .any(|bone_controller| bone_controller.bone == bone && bone_controller.flags.intersects(flags))
+        {
+            return Err(ProcessingBoneError::DuplicateBoneController(input_bone_controller.bone.clone()));
+        }
+
+        // A rotation over a full circle wraps around instead of clamping.
+        let is_rotation = flags.intersects(super::BoneControllerFlags::XR | super::BoneControllerFlags::YR | super::BoneControllerFlags::ZR);
+        if is_rotation && (range.abs() % 360.0) == 0.0 {
+            flags.insert(super::BoneControllerFlags::ROTATION_LOOP);
+        }
+
+        let rest = ((input_bone_controller.rest - input_bone_controller.start) / range * 255.0)
+            .round()
+            .clamp(0.0, 255.0) as i32;
+
+        bone_controllers.push(super::BoneController {
+            bone,
+            flags,
+            start: input_bone_controller.start,
+            end: input_bone_controller.end,
+            rest,
+            input_field: if input_bone_controller.mouth { 4 } else { input_bone_controller.index as i32 },
+        });
+    }
+
+    Ok(bone_controllers)
 }
 
 fn create_ik_chains(
@@ -476,8 +1036,8 @@ fn create_ik_chains(
         if property.ik_chain_auto_play {
             ik_chain.auto_play_lock = Some(super::IkLock {
                 chain: ik_chains.len() as i32,
-                position_weight: property.ik_chain_position_lock,
-                rotation_weight: 1.0 - property.ik_chain_rotation_lock,
+                position_weight: property.ik_chain_position_weight,
+                rotation_weight: property.ik_chain_rotation_weight,
             });
         }
 
@@ -0,0 +1,136 @@
+use std::{
+    fs::read_to_string,
+    path::{Path, PathBuf},
+};
+
+use crate::{
+    utilities::keyvalues::{KeyValue, KeyValueData, parse_key_values},
+    verbose, warn,
+};
+
+/// The maximum amount of patch materials that can be followed.
+const MAX_PATCH_DEPTH: usize = 8;
+
+/// Checks that all materials of the model exist in the game content directory and are usable on a model.
+pub fn validate_materials(content_directory: &Path, model_data: &super::ModelData, model_properties: &super::ModelProperties) {
+    for material in &model_data.materials {
+        let material_file = model_data
+            .material_paths
+            .iter()
+            .find_map(|material_path| find_material_file(content_directory, &format!("{material_path}{material}")));
+
+        let Some(material_file) = material_file else {
+            warn!("Material \"{}\" Was Not Found In The Content Directory!", material);
+            continue;
+        };
+
+        verbose!("Found material \"{}\" at \"{}\".", material, material_file.display());
+
+        let Some(parameters) = load_material_parameters(content_directory, &material_file, material, 0) else {
+            continue;
+        };
+
+        if is_parameter_enabled(&parameters, "$model") == Some(false) {
+            warn!("Material \"{}\" Has \"$model\" Disabled!", material);
+        }
+
+        // Translucent materials are not drawn correctly on a model that is forced opaque.
+        if model_properties.flags.contains(super::ModelFlags::FORCE_OPAQUE)
+            && (is_parameter_enabled(&parameters, "$translucent") == Some(true) || is_parameter_enabled(&parameters, "$additive") == Some(true))
+        {
+            warn!("Material \"{}\" Is Translucent But The Model Is Forced Opaque!", material);
+        }
+    }
+}
+
+/// Finds the vmt file for the material path, falling back to a lowercase path as the game is not case sensitive.
+fn find_material_file(content_directory: &Path, material: &str) -> Option<PathBuf> {
+    let material_file = content_directory.join("materials").join(format!("{material}.vmt"));
+    if material_file.is_file() {
+        return Some(material_file);
+    }
+
+    let lowercase_material_file = content_directory.join("materials").join(format!("{}.vmt", material.to_lowercase()));
+    if lowercase_material_file.is_file() {
+        return Some(lowercase_material_file);
+    }
+
+    None
+}
+
+/// Loads the shader parameters of a vmt file, following patch materials to the material they include.
+fn load_material_parameters(content_directory: &Path, material_file: &Path, material: &str, depth: usize) -> Option<Vec<KeyValue>> {
+    let material_text = match read_to_string(material_file) {
+        Ok(text) => text,
+        Err(error) => {
+            warn!("Failed To Read Material \"{}\": {}!", material, error);
+            return None;
+        }
+    };
+
+    let material_keys = match parse_key_values(&material_text) {
+        Ok(keys) => keys,
+        Err(error) => {
+            warn!("Failed To Parse Material \"{}\": {}!", material, error);
+            return None;
+        }
+    };
+
+    let Some(shader) = material_keys.into_iter().next() else {
+        warn!("Material \"{}\" Has No Shader!", material);
+        return None;
+    };
+
+    if !shader.key.eq_ignore_ascii_case("patch") {
+        return match shader.value {
+            KeyValueData::Block(parameters) => Some(parameters),
+            KeyValueData::Value(_) => {
+                warn!("Material \"{}\" Has No Shader Parameters!", material);
+                None
+            }
+        };
+    }
+
+    if depth >= MAX_PATCH_DEPTH {
+        warn!("Material \"{}\" Has Too Many Patch Includes!", material);
+        return None;
+    }
+
+    let Some(include) = shader.get_value("include") else {
+        warn!("Patch Material \"{}\" Has No Include!", material);
+        return None;
+    };
+
+    let include_file = content_directory.join(include.replace('\\', "/"));
+    if !include_file.is_file() {
+        warn!("Patch Material \"{}\" Include \"{}\" Was Not Found!", material, include);
+        return None;
+    }
+
+    let mut parameters = load_material_parameters(content_directory, &include_file, material, depth + 1)?;
+
+    for patch_block in ["insert", "replace"] {
+        let Some(KeyValueData::Block(patch_parameters)) = shader.get(patch_block) else {
+            continue;
+        };
+
+        for patch_parameter in patch_parameters {
+            match parameters.iter_mut().find(|parameter| parameter.key.eq_ignore_ascii_case(&patch_parameter.key)) {
+                Some(parameter) => parameter.value = patch_parameter.value.clone(),
+                None => parameters.push(patch_parameter.clone()),
+            }
+        }
+    }
+
+    Some(parameters)
+}
+
+/// Returns if the parameter is set to a non zero value, None if the parameter is not set.
+fn is_parameter_enabled(parameters: &[KeyValue], parameter_name: &str) -> Option<bool> {
+    let parameter = parameters.iter().find(|parameter| parameter.key.eq_ignore_ascii_case(parameter_name))?;
+
+    match &parameter.value {
+        KeyValueData::Value(value) => Some(value.trim().parse::<f64>().is_ok_and(|value| value != 0.0)),
+        KeyValueData::Block(_) => None,
+    }
+}
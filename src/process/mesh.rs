@@ -4,6 +4,7 @@ use std::sync::Arc;
 use thiserror::Error as ThisError;
 
 use crate::{
+    debug,
     import::{self, FileData, FileManager},
     input, process,
     utilities::mathematics::{Matrix4, Vector2, Vector3, Vector4, create_space_transform},
@@ -31,6 +32,21 @@ pub fn process_meshes(
 ) -> Result<super::ModelData, ProcessingMeshError> {
     let mut model_data = super::ModelData::default();
 
+    for input_material_path in &input_data.material_paths {
+        let material_path = input_material_path.trim().replace('\\', "/");
+        let material_path = material_path.trim_matches('/');
+
+        if material_path.is_empty() {
+            continue;
+        }
+
+        model_data.material_paths.insert(format!("{material_path}/"));
+    }
+
+    if model_data.material_paths.is_empty() {
+        model_data.material_paths.insert(String::new());
+    }
+
     let mut flex_controller_remap = IndexMap::new();
     for input_flex_controller in &input_data.flex_controllers {
         flex_controller_remap.insert(input_flex_controller.identifier, model_data.flex_data.controllers.len());
@@ -65,7 +81,13 @@ pub fn process_meshes(
                 )
                 .ok_or(ProcessingMeshError::FileNotLoaded(input_model.name.clone(), input_model_group.name.clone()))?;
 
-            let triangle_lists = create_triangle_lists(Arc::clone(&import_file), &mut model_data, input_model, &flex_key_remap);
+            let triangle_lists = create_triangle_lists(
+                Arc::clone(&import_file),
+                &mut model_data,
+                input_model,
+                &input_data.material_renames,
+                &flex_key_remap,
+            );
             if model_data.materials.len() > (i16::MAX as usize) + 1 {
                 return Err(ProcessingMeshError::TooManyMaterials);
             }
@@ -77,7 +99,13 @@ pub fn process_meshes(
                 if triangle_list.triangles.is_empty() {
                     continue;
                 }
-                vertices_remap_links(&mut triangle_list, Arc::clone(&import_file), processed_bone_data, &mut vertex_link_cull_count);
+                vertices_remap_links(
+                    &mut triangle_list,
+                    Arc::clone(&import_file),
+                    processed_bone_data,
+                    input_data.model_properties.static_prop,
+                    &mut vertex_link_cull_count,
+                );
                 optimize_merge_vertices(&mut triangle_list);
                 optimize_vertex_cache(&mut triangle_list);
                 update_bounding_boxes(&triangle_list, &mut model_data, processed_bone_data);
@@ -156,6 +184,7 @@ fn create_triangle_lists(
     import_file: Arc<FileData>,
     model_data: &mut super::ModelData,
     processed_model: &input::Model,
+    material_renames: &[input::MaterialRename],
     flex_key_remap: &IndexMap<usize, usize>,
 ) -> IndexMap<usize, TriangleList> {
     let mut triangle_lists = IndexMap::new();
@@ -166,7 +195,18 @@ fn create_triangle_lists(
         }
 
         for (material, faces) in &import_part.faces {
-            let (material_index, _) = model_data.materials.insert_full(material.clone());
+            let material_name = match material_renames
+                .iter()
+                .find(|rename| rename.source_name == *material && !rename.model_name.is_empty())
+            {
+                Some(material_rename) => {
+                    debug!("Renamed material \"{material}\" to \"{}\"", material_rename.model_name);
+                    &material_rename.model_name
+                }
+                None => material,
+            };
+
+            let (material_index, _) = model_data.materials.insert_full(material_name.clone());
             let triangle_list: &mut TriangleList = triangle_lists.entry(material_index).or_default();
 
             for face in faces {
@@ -271,13 +311,26 @@ fn vertices_remap_links(
     triangle_list: &mut TriangleList,
     import_file: Arc<FileData>,
     processed_bone_data: &super::BoneData,
+    static_prop: bool,
     vertex_link_cull_count: &mut usize,
 ) {
+    // Static props keep the vertices in world space bound to the only bone.
+    if static_prop {
+        for vertex in &mut triangle_list.vertices {
+            vertex.links = vec![TriangleVertexLink { bone: 0, weight: 1.0 }];
+        }
+        return;
+    }
+
     // TODO: Transforms should take into account define bones.
     let mut import_bone_transforms = Vec::with_capacity(import_file.skeleton.len());
     let mut import_bone_processed_bone_mapping = Vec::with_capacity(import_file.skeleton.len());
     for (import_bone_name, import_bone) in &import_file.skeleton {
-        import_bone_processed_bone_mapping.push(processed_bone_data.processed_bones.get_index_of(import_bone_name));
+        import_bone_processed_bone_mapping.push(
+            processed_bone_data
+                .processed_bones
+                .get_index_of(&processed_bone_data.bone_renames.rename(import_bone_name)),
+        );
         if let Some(parent_transform) = import_bone.parent.map(|parent_index| import_bone_transforms[parent_index]) {
             let import_bone_transform = Matrix4::from_rotation_translation(import_bone.rotation, import_bone.location);
             import_bone_transforms.push(parent_transform * import_bone_transform);
@@ -0,0 +1,136 @@
+use thiserror::Error as ThisError;
+
+use crate::{
+    input,
+    utilities::keyvalues::{KeyValue, KeyValueData, write_key_values},
+    warn,
+};
+
+use super::{ModelContents, ModelFlags, ModelProperties};
+
+#[derive(Debug, ThisError)]
+pub enum ProcessingPropertiesError {
+    #[error("Model Can Not Be Both Solid And A Grate")]
+    SolidGrate,
+    #[error("Model Can Not Be Both Forced Opaque And Translucent Two Pass")]
+    OpaqueTranslucent,
+    #[error("Model Mass Is Negative")]
+    NegativeMass,
+    #[error("Model Eye Position Is Not Finite")]
+    InvalidEyePosition,
+    #[error("Model Illumination Position Is Not Finite")]
+    InvalidIlluminationPosition,
+    #[error("Key Value \"{0}\" Has An Invalid Character")]
+    InvalidKeyValue(String),
+}
+
+pub fn process_model_properties(input_data: &input::SourceInput) -> Result<ModelProperties, ProcessingPropertiesError> {
+    let input_properties = &input_data.model_properties;
+
+    let mut contents = ModelContents::empty();
+    contents.set(ModelContents::SOLID, input_properties.contents_solid);
+    contents.set(ModelContents::GRATE, input_properties.contents_grate);
+    contents.set(ModelContents::MONSTER, input_properties.contents_monster);
+    contents.set(ModelContents::LADDER, input_properties.contents_ladder);
+
+    if contents.contains(ModelContents::SOLID | ModelContents::GRATE) {
+        return Err(ProcessingPropertiesError::SolidGrate);
+    }
+
+    let mut flags = ModelFlags::empty();
+    flags.set(ModelFlags::AUTO_GENERATED_HITBOX, input_properties.auto_generated_hitbox);
+    flags.set(ModelFlags::FORCE_OPAQUE, input_properties.force_opaque);
+    flags.set(ModelFlags::TRANSLUCENT_TWO_PASS, input_properties.translucent_two_pass);
+    flags.set(ModelFlags::STATIC_PROP, input_properties.static_prop);
+    flags.set(ModelFlags::OBSOLETE, input_properties.obsolete);
+    flags.set(ModelFlags::NO_FORCED_FADE, input_properties.no_forced_fade);
+    flags.set(ModelFlags::FORCE_PHONEME_CROSS_FADE, input_properties.force_phoneme_cross_fade);
+    flags.set(ModelFlags::AMBIENT_BOOST, input_properties.ambient_boost);
+    flags.set(ModelFlags::DO_NOT_CAST_SHADOWS, input_properties.do_not_cast_shadows);
+    flags.set(ModelFlags::CAST_TEXTURE_SHADOWS, input_properties.cast_texture_shadows);
+    flags.set(
+        ModelFlags::CONSTANT_DIRECTIONAL_LIGHT_DOT,
+        input_properties.define_constant_directional_light_dot,
+    );
+
+    if flags.contains(ModelFlags::FORCE_OPAQUE | ModelFlags::TRANSLUCENT_TWO_PASS) {
+        return Err(ProcessingPropertiesError::OpaqueTranslucent);
+    }
+
+    if input_properties.mass < 0.0 {
+        return Err(ProcessingPropertiesError::NegativeMass);
+    }
+
+    let eye_position = if input_properties.define_eye_position {
+        if !input_properties.eye_position.is_finite() {
+            return Err(ProcessingPropertiesError::InvalidEyePosition);
+        }
+        input_properties.eye_position
+    } else {
+        Default::default()
+    };
+
+    let illumination_position = if input_properties.define_illumination_position {
+        if !input_properties.illumination_position.is_finite() {
+            return Err(ProcessingPropertiesError::InvalidIlluminationPosition);
+        }
+        Some(input_properties.illumination_position)
+    } else {
+        None
+    };
+
+    let surface_property = input_properties.surface_property.trim();
+    let surface_property = if surface_property.is_empty() {
+        warn!("Model Surface Property Is Empty, Using \"default\"!");
+        String::from("default")
+    } else {
+        surface_property.to_string()
+    };
+
+    let constant_directional_light_dot = if input_properties.define_constant_directional_light_dot {
+        (input_properties.constant_directional_light_dot.clamp(0.0, 1.0) * 255.0).round() as u8
+    } else {
+        0
+    };
+
+    let keyvalues = if input_data.keyvalues.is_empty() {
+        String::new()
+    } else {
+        validate_key_values(&input_data.keyvalues)?;
+        write_key_values(&[KeyValue {
+            key: String::from("mdlkeyvalue"),
+            value: KeyValueData::Block(input_data.keyvalues.clone()),
+        }])
+    };
+
+    Ok(ModelProperties {
+        flags,
+        contents,
+        surface_property,
+        eye_position,
+        illumination_position,
+        mass: input_properties.mass,
+        constant_directional_light_dot,
+        keyvalues,
+    })
+}
+
+/// Checks that the keys can be written as a KeyValues text.
+fn validate_key_values(keys: &[KeyValue]) -> Result<(), ProcessingPropertiesError> {
+    for key in keys {
+        if key.key.is_empty() || key.key.contains(['"', '{', '}']) {
+            return Err(ProcessingPropertiesError::InvalidKeyValue(key.key.clone()));
+        }
+
+        match &key.value {
+            KeyValueData::Value(value) => {
+                if value.contains('"') {
+                    return Err(ProcessingPropertiesError::InvalidKeyValue(key.key.clone()));
+                }
+            }
+            KeyValueData::Block(block) => validate_key_values(block)?,
+        }
+    }
+
+    Ok(())
+}
@@ -1,35 +1,285 @@
-use indexmap::IndexMap;
+use indexmap::{IndexMap, IndexSet};
+use std::collections::VecDeque;
 use thiserror::Error as ThisError;
 
-use crate::input;
+use crate::{input, utilities::mathematics::Matrix4, warn};
 
 #[derive(Debug, ThisError)]
 pub enum ProcessingSequenceError {
     #[error("Model Has Too Many Sequences")]
     TooManySequences,
+    #[error("Model Has Too Many Pose Parameters")]
+    TooManyPoseParameters,
+    #[error("Duplicate Pose Parameter Name \"{0}\"")]
+    DuplicatePoseParameter(String),
+    #[error("Pose Parameter \"{0}\" Has The Same Start And End")]
+    EmptyPoseParameterRange(String),
+    #[error("Pose Parameter \"{0}\" Loop Range Must Be Greater Than Zero")]
+    InvalidPoseParameterLoop(String),
+    #[error("Sequence \"{0}\" Has Rows With Different Amounts Of Animations")]
+    UnevenBlendGrid(String),
+    #[error("Sequence \"{0}\" Blends More Than One Animation On An Axis Without A Pose Parameter")]
+    MissingBlendParameter(String),
+    #[error("Sequence \"{0}\" Uses Pose Parameter \"{1}\" That Doesn't Exist")]
+    PoseParameterNotFound(String, String),
+    #[error("Sequence \"{0}\" Has A Negative Fade Time")]
+    NegativeFadeTime(String),
+    #[error("Sequence \"{0}\" Has A Negative Activity Weight")]
+    NegativeActivityWeight(String),
+    #[error("Sequence \"{0}\" Transition Has No Entry Node")]
+    MissingEntryNode(String),
+    #[error("Model Has Too Many Sequence Nodes")]
+    TooManyNodes,
+    #[error("Sequence \"{0}\" Has An Event With No Name")]
+    EmptyEvent(String),
+    #[error("Sequence \"{0}\" Event \"{1}\" Is Outside Of The Sequence")]
+    EventOutOfRange(String, String),
+    #[error("Sequence \"{0}\" Event \"{1}\" Options Are Longer Than 63 Characters")]
+    EventOptionsTooLong(String, String),
+    #[error("Sequence \"{0}\" Layers Sequence \"{1}\" That Doesn't Exist")]
+    LayerSequenceNotFound(String, String),
+    #[error("Sequence \"{0}\" Can Not Layer Itself")]
+    SelfLayer(String),
+    #[error("Sequence \"{0}\" Layer \"{1}\" Start, Peak, Tail And End Must Be In Order")]
+    InvalidLayerRange(String, String),
+    #[error("Duplicate Weight List Name \"{0}\"")]
+    DuplicateWeightList(String),
+    #[error("Weight List \"{0}\" Has A Weight Outside Of 0 To 1")]
+    InvalidBoneWeight(String),
+    #[error("Sequence \"{0}\" Uses Weight List \"{1}\" That Doesn't Exist")]
+    WeightListNotFound(String, String),
+    #[error("Sequence \"{0}\" Uses Ik Chain \"{1}\" That Doesn't Exist")]
+    IkChainNotFound(String, String),
+    #[error("Sequence \"{0}\" Ik Rule Touches Bone \"{1}\" That Doesn't Exist")]
+    IkBoneNotFound(String, String),
+    #[error("Sequence \"{0}\" Ik Rule On Chain \"{1}\" Start, Peak, Tail And End Must Be In Order Within The Frames")]
+    InvalidIkRuleRange(String, String),
+    #[error("Sequence \"{0}\" Ik Rule On Chain \"{1}\" Contact Frame Is Outside Of The Frames")]
+    InvalidIkContact(String, String),
+    #[error("Sequence \"{0}\" Can Not Have Ik Rules As It Uses Delta Animation \"{1}\"")]
+    IkRuleOnDelta(String, String),
+    #[error("Animation \"{0}\" Is Used By More Than One Sequence With Ik Rules")]
+    ConflictingIkRules(String),
+}
+
+/// The maximum length of the event options, including the null terminator.
+const MAX_EVENT_OPTIONS_LENGTH: usize = 64;
+
+/// The maximum amount of pose parameters the game supports.
+const MAX_POSE_PARAMETERS: usize = 24;
+
+pub fn process_pose_parameters(input_data: &input::SourceInput) -> Result<IndexMap<String, super::PoseParameter>, ProcessingSequenceError> {
+    let mut processed_pose_parameters = IndexMap::with_capacity(input_data.pose_parameters.len());
+
+    for input_pose_parameter in &input_data.pose_parameters {
+        if processed_pose_parameters.contains_key(&input_pose_parameter.name) {
+            return Err(ProcessingSequenceError::DuplicatePoseParameter(input_pose_parameter.name.clone()));
+        }
+
+        if input_pose_parameter.start == input_pose_parameter.end {
+            return Err(ProcessingSequenceError::EmptyPoseParameterRange(input_pose_parameter.name.clone()));
+        }
+
+        if input_pose_parameter.define_loop && input_pose_parameter.loop_range <= 0.0 {
+            return Err(ProcessingSequenceError::InvalidPoseParameterLoop(input_pose_parameter.name.clone()));
+        }
+
+        processed_pose_parameters.insert(
+            input_pose_parameter.name.clone(),
+            super::PoseParameter {
+                start: input_pose_parameter.start,
+                end: input_pose_parameter.end,
+                loop_range: input_pose_parameter.define_loop.then_some(input_pose_parameter.loop_range),
+            },
+        );
+    }
+
+    if processed_pose_parameters.len() > MAX_POSE_PARAMETERS {
+        return Err(ProcessingSequenceError::TooManyPoseParameters);
+    }
+
+    Ok(processed_pose_parameters)
 }
 
 pub fn process_sequences(
     input_data: &input::SourceInput,
-    remapped_animations: &IndexMap<usize, usize>,
-) -> Result<IndexMap<String, super::Sequence>, ProcessingSequenceError> {
+    bone_data: &super::BoneData,
+    animation_data: &mut super::AnimationData,
+    pose_parameters: &IndexMap<String, super::PoseParameter>,
+) -> Result<super::SequenceData, ProcessingSequenceError> {
     let mut processed_sequences = IndexMap::with_capacity(input_data.sequences.len());
+    let mut nodes = IndexSet::new();
+    let weight_lists = create_weight_lists(&input_data.weight_lists, bone_data)?;
 
     for input_sequence in input_data.sequences.iter() {
         let processed_sequence_name = input_sequence.name.clone();
         debug_assert!(!processed_sequences.contains_key(&processed_sequence_name));
 
+        let column_count = input_sequence.animations[0].len();
+        if input_sequence.animations.iter().any(|row| row.len() != column_count) {
+            return Err(ProcessingSequenceError::UnevenBlendGrid(processed_sequence_name));
+        }
+
         let mut processed_sequence = super::Sequence {
-            animations: vec![vec![0; input_sequence.animations[0].len()]; input_sequence.animations.len()],
+            animations: vec![vec![0; column_count]; input_sequence.animations.len()],
+            ..Default::default()
         };
 
         for (row_index, row_value) in input_sequence.animations.iter().enumerate() {
             for (column_index, column_value) in row_value.iter().enumerate() {
-                let mapped_animation_index = *remapped_animations.get(column_value).unwrap();
+                let mapped_animation_index = *animation_data.remapped_animations.get(column_value).unwrap();
                 processed_sequence.animations[row_index][column_index] = mapped_animation_index as i16;
             }
         }
 
+        let axis_sizes = [column_count, input_sequence.animations.len()];
+        for (axis_index, input_blend_axis) in input_sequence.blend_axes.iter().enumerate() {
+            if input_blend_axis.parameter.is_empty() {
+                if axis_sizes[axis_index] > 1 {
+                    return Err(ProcessingSequenceError::MissingBlendParameter(processed_sequence_name));
+                }
+                continue;
+            }
+
+            let (parameter, _, pose_parameter) = pose_parameters
+                .get_full(&input_blend_axis.parameter)
+                .ok_or_else(|| ProcessingSequenceError::PoseParameterNotFound(processed_sequence_name.clone(), input_blend_axis.parameter.clone()))?;
+
+            let (start, end) = if input_blend_axis.define_range {
+                (input_blend_axis.start, input_blend_axis.end)
+            } else {
+                (pose_parameter.start, pose_parameter.end)
+            };
+
+            processed_sequence.blend_axes[axis_index] = Some(super::BlendAxis { parameter, start, end });
+        }
+
+        processed_sequence.flags.set(super::SequenceFlags::LOOPING, input_sequence.looping);
+        processed_sequence.flags.set(super::SequenceFlags::SNAP, input_sequence.snap);
+        processed_sequence.flags.set(super::SequenceFlags::DELTA, input_sequence.delta);
+        processed_sequence.flags.set(super::SequenceFlags::AUTO_PLAY, input_sequence.auto_play);
+        processed_sequence.flags.set(super::SequenceFlags::REALTIME, input_sequence.realtime);
+        processed_sequence.flags.set(super::SequenceFlags::HIDDEN, input_sequence.hidden);
+
+        if !input_sequence.activity.is_empty() {
+            if input_sequence.activity_weight < 0 {
+                return Err(ProcessingSequenceError::NegativeActivityWeight(processed_sequence_name));
+            }
+
+            processed_sequence.activity = Some((input_sequence.activity.clone(), input_sequence.activity_weight));
+        }
+
+        if input_sequence.fade_in_time < 0.0 || input_sequence.fade_out_time < 0.0 {
+            return Err(ProcessingSequenceError::NegativeFadeTime(processed_sequence_name));
+        }
+
+        processed_sequence.fade_in_time = input_sequence.fade_in_time;
+        processed_sequence.fade_out_time = input_sequence.fade_out_time;
+
+        if input_sequence.define_transition {
+            if input_sequence.entry_node.is_empty() {
+                return Err(ProcessingSequenceError::MissingEntryNode(processed_sequence_name));
+            }
+
+            let exit_node = if input_sequence.exit_node.is_empty() {
+                &input_sequence.entry_node
+            } else {
+                &input_sequence.exit_node
+            };
+
+            processed_sequence.entry_node = nodes.insert_full(input_sequence.entry_node.clone()).0 + 1;
+            processed_sequence.exit_node = nodes.insert_full(exit_node.clone()).0 + 1;
+            processed_sequence.reverse_transition = input_sequence.reverse_transition;
+        }
+
+        let first_animation_frame_count = animation_data.processed_animations[processed_sequence.animations[0][0] as usize].frame_count;
+        for input_event in &input_sequence.events {
+            if input_event.event.is_empty() {
+                return Err(ProcessingSequenceError::EmptyEvent(processed_sequence_name));
+            }
+
+            let cycle = if input_event.use_cycle {
+                input_event.cycle
+            } else {
+                input_event.frame as f64 / (first_animation_frame_count - 1).max(1) as f64
+            };
+
+            if !(0.0..=1.0).contains(&cycle) || (!input_event.use_cycle && input_event.frame >= first_animation_frame_count) {
+                return Err(ProcessingSequenceError::EventOutOfRange(processed_sequence_name, input_event.event.clone()));
+            }
+
+            if input_event.options.len() >= MAX_EVENT_OPTIONS_LENGTH {
+                return Err(ProcessingSequenceError::EventOptionsTooLong(processed_sequence_name, input_event.event.clone()));
+            }
+
+            processed_sequence.events.push(super::SequenceEvent {
+                cycle,
+                id: input_event.event.parse().ok(),
+                name: input_event.event.clone(),
+                options: input_event.options.clone(),
+            });
+        }
+        processed_sequence.events.sort_by(|from, to| from.cycle.total_cmp(&to.cycle));
+
+        processed_sequence.weight_list = if input_sequence.weight_list.is_empty() {
+            vec![1.0; bone_data.processed_bones.len()]
+        } else {
+            weight_lists
+                .get(&input_sequence.weight_list)
+                .ok_or_else(|| ProcessingSequenceError::WeightListNotFound(processed_sequence_name.clone(), input_sequence.weight_list.clone()))?
+                .clone()
+        };
+
+        // Every animation of the sequence has the same rules, computed from each animation.
+        let mut ik_rule_animations = processed_sequence
+            .animations
+            .iter()
+            .flatten()
+            .map(|&animation| animation as usize)
+            .collect::<Vec<_>>();
+        ik_rule_animations.sort_unstable();
+        ik_rule_animations.dedup();
+        if input_sequence.ik_rules.is_empty() {
+            ik_rule_animations.clear();
+        }
+        for animation_index in ik_rule_animations {
+            let (animation_name, animation) = animation_data.processed_animations.get_index_mut(animation_index).unwrap();
+            if !animation.ik_rules.is_empty() {
+                return Err(ProcessingSequenceError::ConflictingIkRules(animation_name.clone()));
+            }
+
+            if animation.flags.contains(super::AnimationFlags::DELTA) {
+                return Err(ProcessingSequenceError::IkRuleOnDelta(processed_sequence_name, animation_name.clone()));
+            }
+
+            for input_ik_rule in &input_sequence.ik_rules {
+                let ik_rule = create_ik_rule(&processed_sequence_name, input_ik_rule, animation, bone_data)?;
+                animation.ik_rules.push(ik_rule);
+            }
+        }
+        processed_sequence.ik_rule_count = input_sequence.ik_rules.len();
+
+        for input_ik_lock in &input_sequence.ik_locks {
+            let chain = bone_data
+                .ik_chains
+                .get_index_of(&input_ik_lock.chain)
+                .ok_or_else(|| ProcessingSequenceError::IkChainNotFound(processed_sequence_name.clone(), input_ik_lock.chain.clone()))?;
+
+            processed_sequence.ik_locks.push(super::IkLock {
+                chain: chain as i32,
+                position_weight: input_ik_lock.position_weight,
+                rotation_weight: input_ik_lock.rotation_weight,
+            });
+        }
+
+        for input_auto_layer in &input_sequence.auto_layers {
+            let auto_layer = create_auto_layer(input_data, input_sequence, input_auto_layer, first_animation_frame_count, pose_parameters)?;
+            if auto_layer.flags.contains(super::AutoLayerFlags::LOCAL) {
+                processed_sequence.flags.insert(super::SequenceFlags::LOCAL);
+            }
+            processed_sequence.auto_layers.push(auto_layer);
+        }
+
         processed_sequences.insert(processed_sequence_name, processed_sequence);
     }
 
@@ -37,5 +287,273 @@ pub fn process_sequences(
         return Err(ProcessingSequenceError::TooManySequences);
     }
 
-    Ok(processed_sequences)
+    if nodes.len() > u8::MAX as usize {
+        return Err(ProcessingSequenceError::TooManyNodes);
+    }
+
+    let node_transitions = create_node_transitions(nodes.len(), &processed_sequences);
+
+    Ok(super::SequenceData {
+        processed_sequences,
+        nodes,
+        node_transitions,
+    })
+}
+
+/// Creates the weight of every bone for each weight list, bones without a weight use the weight of the parent.
+fn create_weight_lists(input_weight_lists: &[input::WeightList], bone_data: &super::BoneData) -> Result<IndexMap<String, Vec<f64>>, ProcessingSequenceError> {
+    let mut weight_lists = IndexMap::with_capacity(input_weight_lists.len());
+
+    for input_weight_list in input_weight_lists {
+        if weight_lists.contains_key(&input_weight_list.name) {
+            return Err(ProcessingSequenceError::DuplicateWeightList(input_weight_list.name.clone()));
+        }
+
+        let mut bone_weights = vec![None; bone_data.processed_bones.len()];
+        for bone_weight in &input_weight_list.weights {
+            if !(0.0..=1.0).contains(&bone_weight.weight) {
+                return Err(ProcessingSequenceError::InvalidBoneWeight(input_weight_list.name.clone()));
+            }
+
+            match bone_data.processed_bones.get_index_of(&bone_weight.bone) {
+                Some(bone_index) => bone_weights[bone_index] = Some(bone_weight.weight),
+                None => warn!(
+                    "Bone \"{}\" does not exist to weight in weight list \"{}\"",
+                    bone_weight.bone, input_weight_list.name
+                ),
+            }
+        }
+
+        // Parents are always before children, so the parent weight is already set.
+        let mut weights = Vec::with_capacity(bone_weights.len());
+        for (bone_index, bone_weight) in bone_weights.into_iter().enumerate() {
+            let inherited_weight = bone_data.processed_bones[bone_index].parent.map_or(0.0, |parent_index| weights[parent_index]);
+            weights.push(bone_weight.unwrap_or(inherited_weight));
+        }
+
+        weight_lists.insert(input_weight_list.name.clone(), weights);
+    }
+
+    Ok(weight_lists)
+}
+
+fn create_ik_rule(
+    sequence_name: &str,
+    input_ik_rule: &input::IkRule,
+    animation: &super::Animation,
+    bone_data: &super::BoneData,
+) -> Result<super::IkRule, ProcessingSequenceError> {
+    let (chain, _, ik_chain) = bone_data
+        .ik_chains
+        .get_full(&input_ik_rule.chain)
+        .ok_or_else(|| ProcessingSequenceError::IkChainNotFound(String::from(sequence_name), input_ik_rule.chain.clone()))?;
+    let foot_bone = ik_chain.links[2] as usize;
+
+    let last_frame = animation.frame_count - 1;
+    let (start, peak, tail, end) = if input_ik_rule.define_range {
+        (input_ik_rule.start, input_ik_rule.peak, input_ik_rule.tail, input_ik_rule.end)
+    } else {
+        (0, 0, last_frame, last_frame)
+    };
+
+    if start > peak || peak > tail || tail > end || end > last_frame {
+        return Err(ProcessingSequenceError::InvalidIkRuleRange(
+            String::from(sequence_name),
+            input_ik_rule.chain.clone(),
+        ));
+    }
+
+    if input_ik_rule.contact > last_frame {
+        return Err(ProcessingSequenceError::InvalidIkContact(
+            String::from(sequence_name),
+            input_ik_rule.chain.clone(),
+        ));
+    }
+
+    let frame_to_cycle = |frame: usize| frame as f64 / last_frame.max(1) as f64;
+    let mut ik_rule = super::IkRule {
+        chain,
+        height: input_ik_rule.height,
+        radius: input_ik_rule.radius,
+        floor: input_ik_rule.floor,
+        start_frame: start,
+        start: frame_to_cycle(start),
+        peak: frame_to_cycle(peak),
+        tail: frame_to_cycle(tail),
+        end: frame_to_cycle(end),
+        contact: frame_to_cycle(input_ik_rule.contact),
+        ..Default::default()
+    };
+
+    ik_rule.kind = match input_ik_rule.kind {
+        input::IkRuleKind::Touch => {
+            let bone = bone_data
+                .processed_bones
+                .get_index_of(&input_ik_rule.bone)
+                .ok_or_else(|| ProcessingSequenceError::IkBoneNotFound(String::from(sequence_name), input_ik_rule.bone.clone()))?;
+            ik_rule.bone = Some(bone);
+            super::IkRuleKind::Touch
+        }
+        input::IkRuleKind::Footstep => super::IkRuleKind::Footstep,
+        // Releasing a chain does not place the chain so there is no error.
+        input::IkRuleKind::Release => {
+            return Ok(super::IkRule {
+                kind: super::IkRuleKind::Release,
+                ..ik_rule
+            });
+        }
+        input::IkRuleKind::Unlatch => {
+            return Ok(super::IkRule {
+                kind: super::IkRuleKind::Unlatch,
+                ..ik_rule
+            });
+        }
+    };
+
+    for frame in start..=end {
+        let world_transforms = create_frame_world_transforms(animation, bone_data, frame);
+        let reference_transform = ik_rule.bone.map_or(Matrix4::IDENTITY, |bone| world_transforms[bone]);
+        let (_, rotation, position) = (reference_transform.inverse() * world_transforms[foot_bone]).to_scale_rotation_translation();
+        ik_rule.errors.push((position, rotation));
+    }
+
+    let contact_error = ik_rule.errors[input_ik_rule.contact.clamp(start, end) - start];
+    ik_rule.position = contact_error.0;
+    ik_rule.rotation = contact_error.1;
+
+    let contact_height = ik_rule.position.z;
+    for (position, _) in &ik_rule.errors {
+        ik_rule.drop = ik_rule.drop.max(contact_height - position.z);
+        ik_rule.top = ik_rule.top.max(position.z - contact_height);
+    }
+
+    Ok(ik_rule)
+}
+
+/// Creates the transforms in model space of every bone for a frame of the animation.
+fn create_frame_world_transforms(animation: &super::Animation, bone_data: &super::BoneData, frame: usize) -> Vec<Matrix4> {
+    let section_index = frame / animation.section_frame_count;
+    let section_frame = frame - section_index * animation.section_frame_count;
+    let section = &animation.sections[section_index];
+
+    let mut world_transforms: Vec<Matrix4> = Vec::with_capacity(bone_data.processed_bones.len());
+    for (bone_index, bone) in bone_data.processed_bones.values().enumerate() {
+        let (position, rotation) = section
+            .iter()
+            .find(|bone_data| bone_data.bone as usize == bone_index)
+            .map_or((bone.location, bone.rotation), |bone_data| {
+                (bone_data.raw_position[section_frame], bone_data.raw_rotation[section_frame])
+            });
+
+        let local_transform = Matrix4::from_rotation_translation(rotation, position);
+        world_transforms.push(match bone.parent {
+            Some(parent_index) => world_transforms[parent_index] * local_transform,
+            None => local_transform,
+        });
+    }
+
+    world_transforms
+}
+
+fn create_auto_layer(
+    input_data: &input::SourceInput,
+    input_sequence: &input::Sequence,
+    input_auto_layer: &input::AutoLayer,
+    frame_count: usize,
+    pose_parameters: &IndexMap<String, super::PoseParameter>,
+) -> Result<super::AutoLayer, ProcessingSequenceError> {
+    if input_auto_layer.sequence == input_sequence.name {
+        return Err(ProcessingSequenceError::SelfLayer(input_sequence.name.clone()));
+    }
+
+    let sequence = input_data
+        .sequences
+        .iter()
+        .position(|sequence| sequence.name == input_auto_layer.sequence)
+        .ok_or_else(|| ProcessingSequenceError::LayerSequenceNotFound(input_sequence.name.clone(), input_auto_layer.sequence.clone()))?;
+
+    let mut auto_layer = super::AutoLayer {
+        sequence,
+        ..Default::default()
+    };
+
+    // A layer that is not blended is at full weight for the whole sequence.
+    if !input_auto_layer.blend {
+        return Ok(auto_layer);
+    }
+
+    let blend_points = [input_auto_layer.start, input_auto_layer.peak, input_auto_layer.tail, input_auto_layer.end];
+    if blend_points.windows(2).any(|points| points[0] > points[1]) {
+        return Err(ProcessingSequenceError::InvalidLayerRange(
+            input_sequence.name.clone(),
+            input_auto_layer.sequence.clone(),
+        ));
+    }
+
+    // The blend points are remapped from frames or pose parameter values to 0 to 1.
+    let (range_start, range_length) = if input_auto_layer.define_pose_parameter {
+        let (pose_parameter_index, _, pose_parameter) = pose_parameters
+            .get_full(&input_auto_layer.pose_parameter)
+            .ok_or_else(|| ProcessingSequenceError::PoseParameterNotFound(input_sequence.name.clone(), input_auto_layer.pose_parameter.clone()))?;
+        auto_layer.pose_parameter = Some(pose_parameter_index);
+        auto_layer.flags.insert(super::AutoLayerFlags::POSE);
+        (pose_parameter.start, pose_parameter.end - pose_parameter.start)
+    } else {
+        (0.0, (frame_count - 1).max(1) as f64)
+    };
+
+    let [start, peak, tail, end] = blend_points.map(|point| (point - range_start) / range_length);
+    auto_layer.start = start;
+    auto_layer.peak = peak;
+    auto_layer.tail = tail;
+    auto_layer.end = end;
+
+    auto_layer.flags.set(super::AutoLayerFlags::SPLINE, input_auto_layer.spline);
+    auto_layer.flags.set(super::AutoLayerFlags::XFADE, input_auto_layer.cross_fade);
+    auto_layer.flags.set(super::AutoLayerFlags::NO_BLEND, input_auto_layer.no_blend);
+    auto_layer.flags.set(super::AutoLayerFlags::LOCAL, input_auto_layer.local);
+
+    Ok(auto_layer)
+}
+
+/// Creates the table of the next node to go to for the shortest path from every node to every other node.
+fn create_node_transitions(node_count: usize, processed_sequences: &IndexMap<String, super::Sequence>) -> Vec<Vec<u8>> {
+    let mut connected_nodes = vec![Vec::new(); node_count];
+    for processed_sequence in processed_sequences.values() {
+        if processed_sequence.entry_node == processed_sequence.exit_node {
+            continue;
+        }
+
+        let entry_node = processed_sequence.entry_node - 1;
+        let exit_node = processed_sequence.exit_node - 1;
+        connected_nodes[entry_node].push(exit_node);
+        if processed_sequence.reverse_transition {
+            connected_nodes[exit_node].push(entry_node);
+        }
+    }
+
+    let mut node_transitions = vec![vec![0; node_count]; node_count];
+    for (from_node, transitions) in node_transitions.iter_mut().enumerate() {
+        transitions[from_node] = (from_node + 1) as u8;
+
+        // The first step taken to reach each node is carried through the search.
+        let mut queued_nodes = VecDeque::new();
+        for &next_node in &connected_nodes[from_node] {
+            if transitions[next_node] == 0 {
+                transitions[next_node] = (next_node + 1) as u8;
+                queued_nodes.push_back(next_node);
+            }
+        }
+
+        while let Some(current_node) = queued_nodes.pop_front() {
+            for &next_node in &connected_nodes[current_node] {
+                if transitions[next_node] == 0 {
+                    transitions[next_node] = transitions[current_node];
+                    queued_nodes.push_back(next_node);
+                }
+            }
+        }
+    }
+
+    node_transitions
 }
@@ -0,0 +1,263 @@
+use thiserror::Error as ThisError;
+
+/// A key in a KeyValues text with either a value or a block of keys.
+#[derive(Clone, Debug)]
+pub struct KeyValue {
+    pub key: String,
+    pub value: KeyValueData,
+}
+
+#[derive(Clone, Debug)]
+pub enum KeyValueData {
+    Value(String),
+    Block(Vec<KeyValue>),
+}
+
+impl KeyValue {
+    /// Returns the first value in the block that matches the key, keys are case insensitive.
+    pub fn get(&self, key: &str) -> Option<&KeyValueData> {
+        match &self.value {
+            KeyValueData::Value(_) => None,
+            KeyValueData::Block(block) => block.iter().find(|entry| entry.key.eq_ignore_ascii_case(key)).map(|entry| &entry.value),
+        }
+    }
+
+    /// Returns the first string value in the block that matches the key.
+    pub fn get_value(&self, key: &str) -> Option<&str> {
+        match self.get(key) {
+            Some(KeyValueData::Value(value)) => Some(value),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, ThisError)]
+pub enum ParseKeyValuesError {
+    #[error("Unfinished Quote Block At Line {0}")]
+    UnfinishedQuoteBlock(usize),
+    #[error("Unexpected Opening Brace At Line {0}")]
+    UnexpectedOpeningBrace(usize),
+    #[error("Unexpected Closing Brace At Line {0}")]
+    UnexpectedClosingBrace(usize),
+    #[error("Key \"{0}\" Is Missing A Value")]
+    MissingValue(String),
+    #[error("Block \"{0}\" Is Not Closed")]
+    UnclosedBlock(String),
+}
+
+enum Token {
+    String(String),
+    Open,
+    Close,
+}
+
+/// Parses a KeyValues text to a list of keys.
+pub fn parse_key_values(text: &str) -> Result<Vec<KeyValue>, ParseKeyValuesError> {
+    let mut tokens = tokenize(text)?.into_iter();
+
+    // The stack of opened blocks with the key that opened them.
+    let mut blocks: Vec<(String, Vec<KeyValue>)> = vec![(String::new(), Vec::new())];
+
+    while let Some((line, token)) = tokens.next() {
+        match token {
+            Token::String(key) => match tokens.next() {
+                Some((_, Token::String(value))) => {
+                    blocks.last_mut().unwrap().1.push(KeyValue {
+                        key,
+                        value: KeyValueData::Value(value),
+                    });
+                }
+                Some((_, Token::Open)) => {
+                    blocks.push((key, Vec::new()));
+                }
+                Some((_, Token::Close)) | None => return Err(ParseKeyValuesError::MissingValue(key)),
+            },
+            Token::Open => return Err(ParseKeyValuesError::UnexpectedOpeningBrace(line)),
+            Token::Close => {
+                if blocks.len() == 1 {
+                    return Err(ParseKeyValuesError::UnexpectedClosingBrace(line));
+                }
+
+                let (key, block) = blocks.pop().unwrap();
+                blocks.last_mut().unwrap().1.push(KeyValue {
+                    key,
+                    value: KeyValueData::Block(block),
+                });
+            }
+        }
+    }
+
+    if blocks.len() > 1 {
+        return Err(ParseKeyValuesError::UnclosedBlock(blocks.pop().unwrap().0));
+    }
+
+    Ok(blocks.pop().unwrap().1)
+}
+
+fn tokenize(text: &str) -> Result<Vec<(usize, Token)>, ParseKeyValuesError> {
+    let mut tokens = Vec::new();
+
+    for (line_index, line) in text.lines().enumerate() {
+        let line_number = line_index + 1;
+        let mut characters = line.chars().peekable();
+
+        while let Some(character) = characters.next() {
+            match character {
+                '{' => tokens.push((line_number, Token::Open)),
+                '}' => tokens.push((line_number, Token::Close)),
+                '"' => {
+                    let mut quote = String::new();
+                    loop {
+                        match characters.next() {
+                            Some('"') => break,
+                            // Only quotes and backslashes are escaped so paths with backslashes are kept.
+                            Some('\\') if matches!(characters.peek(), Some('"' | '\\')) => quote.push(characters.next().unwrap()),
+                            Some(quote_character) => quote.push(quote_character),
+                            None => return Err(ParseKeyValuesError::UnfinishedQuoteBlock(line_number)),
+                        }
+                    }
+                    tokens.push((line_number, Token::String(quote)));
+                }
+                '/' if characters.peek() == Some(&'/') => break,
+                character if character.is_whitespace() => continue,
+                character => {
+                    let mut text = String::from(character);
+                    while let Some(&text_character) = characters.peek() {
+                        if text_character.is_whitespace() || matches!(text_character, '{' | '}' | '"') {
+                            break;
+                        }
+                        text.push(text_character);
+                        characters.next();
+                    }
+
+                    // Platform conditionals are ignored as all keys are used.
+                    if text.starts_with('[') && text.ends_with(']') {
+                        continue;
+                    }
+
+                    tokens.push((line_number, Token::String(text)));
+                }
+            }
+        }
+    }
+
+    Ok(tokens)
+}
+
+/// Writes a list of keys to a KeyValues text.
+pub fn write_key_values(keys: &[KeyValue]) -> String {
+    let mut text = String::new();
+    write_block(&mut text, keys, 0);
+    text
+}
+
+fn write_block(text: &mut String, keys: &[KeyValue], depth: usize) {
+    let indent = "\t".repeat(depth);
+    for key in keys {
+        let escaped_key = escape_quoted(&key.key);
+        match &key.value {
+            KeyValueData::Value(value) => {
+                text.push_str(&format!("{indent}\"{escaped_key}\" \"{}\"\n", escape_quoted(value)));
+            }
+            KeyValueData::Block(block) => {
+                text.push_str(&format!("{indent}\"{escaped_key}\"\n{indent}{{\n"));
+                write_block(text, block, depth + 1);
+                text.push_str(&format!("{indent}}}\n"));
+            }
+        }
+    }
+}
+
+fn escape_quoted(text: &str) -> String {
+    text.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE_KEY_VALUES: &str = r#"
+// Physics properties for a prop
+prop_data
+{
+	"base" "Wooden.Medium"
+	"health" "25" // Trailing comment
+	physgun_interactions
+	{
+		"onfirstimpact" "break"
+	}
+	"dmg.bullets" "1" [$X360]
+}
+"#;
+
+    fn value(key: &KeyValue) -> &str {
+        match &key.value {
+            KeyValueData::Value(value) => value,
+            KeyValueData::Block(_) => panic!("Key \"{}\" should be a value", key.key),
+        }
+    }
+
+    #[test]
+    fn parses_nested_blocks() {
+        let keys = parse_key_values(SAMPLE_KEY_VALUES).unwrap();
+        assert_eq!(keys.len(), 1);
+        assert_eq!(keys[0].key, "prop_data");
+        assert_eq!(keys[0].get_value("BASE"), Some("Wooden.Medium"));
+        assert_eq!(keys[0].get_value("health"), Some("25"));
+        assert_eq!(keys[0].get_value("dmg.bullets"), Some("1"));
+
+        let Some(KeyValueData::Block(interactions)) = keys[0].get("physgun_interactions") else {
+            panic!("physgun_interactions should be a block");
+        };
+        assert_eq!(interactions.len(), 1);
+        assert_eq!(interactions[0].key, "onfirstimpact");
+        assert_eq!(value(&interactions[0]), "break");
+    }
+
+    #[test]
+    fn parses_quoted_and_escaped_tokens() {
+        let keys = parse_key_values(r#""key with spaces" "value {with} // braces" "quote" "say \"hi\"" path "models\props\crate""#).unwrap();
+        assert_eq!(keys[0].key, "key with spaces");
+        assert_eq!(value(&keys[0]), "value {with} // braces");
+        assert_eq!(value(&keys[1]), "say \"hi\"");
+        assert_eq!(keys[2].key, "path");
+        assert_eq!(value(&keys[2]), "models\\props\\crate");
+
+        let keys = parse_key_values(r#""escaped" "back\\slash""#).unwrap();
+        assert_eq!(value(&keys[0]), "back\\slash");
+    }
+
+    #[test]
+    fn ignores_comments() {
+        let keys = parse_key_values("// \"commented\" \"out\"\nkey value // \"trailing\" \"comment\"\n").unwrap();
+        assert_eq!(keys.len(), 1);
+        assert_eq!(keys[0].key, "key");
+        assert_eq!(value(&keys[0]), "value");
+    }
+
+    #[test]
+    fn rejects_malformed_text() {
+        assert!(matches!(parse_key_values("\"key\" \"value"), Err(ParseKeyValuesError::UnfinishedQuoteBlock(1))));
+        assert!(matches!(parse_key_values("key value\n{"), Err(ParseKeyValuesError::UnexpectedOpeningBrace(2))));
+        assert!(matches!(parse_key_values("}"), Err(ParseKeyValuesError::UnexpectedClosingBrace(1))));
+        assert!(matches!(parse_key_values("block { key }"), Err(ParseKeyValuesError::MissingValue(key)) if key == "key"));
+        assert!(matches!(parse_key_values("lonely"), Err(ParseKeyValuesError::MissingValue(key)) if key == "lonely"));
+        assert!(matches!(parse_key_values("block { key value"), Err(ParseKeyValuesError::UnclosedBlock(key)) if key == "block"));
+    }
+
+    #[test]
+    fn written_text_parses_to_the_same_keys() {
+        let keys = parse_key_values(SAMPLE_KEY_VALUES).unwrap();
+        let mut keys_with_escapes = keys.clone();
+        keys_with_escapes.push(KeyValue {
+            key: String::from("quoted \"key\""),
+            value: KeyValueData::Value(String::from("ends with \\")),
+        });
+
+        let written = write_key_values(&keys_with_escapes);
+        let parsed = parse_key_values(&written).unwrap();
+        assert_eq!(write_key_values(&parsed), written);
+        assert_eq!(parsed[1].key, "quoted \"key\"");
+        assert_eq!(value(&parsed[1]), "ends with \\");
+    }
+}
@@ -1,2 +1,4 @@
+pub mod keyvalues;
 pub mod logging;
 pub mod mathematics;
+pub mod vrd;
@@ -0,0 +1,215 @@
+use thiserror::Error as ThisError;
+
+use crate::{
+    input::{AimAtProceduralBone, ProceduralBone, ProceduralTrigger, QuaternionProceduralBone},
+    utilities::mathematics::Vector3,
+};
+
+#[derive(Debug, ThisError)]
+pub enum ParseVRDError {
+    #[error("Expected A Number At Line {0}")]
+    ExpectedNumber(usize),
+    #[error("Expected A Bone Name At Line {0}")]
+    ExpectedBoneName(usize),
+    #[error("Unknown Command \"{0}\" At Line {1}")]
+    UnknownCommand(String, usize),
+    #[error("Command \"{0}\" At Line {1} Is Not In A Procedural Bone")]
+    NoProceduralBone(String, usize),
+    #[error("Unexpected Argument \"{0}\" At Line {1}")]
+    UnexpectedArgument(String, usize),
+}
+
+/// Parses a VRD text to a list of procedural bones.
+pub fn parse_vrd(text: &str) -> Result<Vec<ProceduralBone>, ParseVRDError> {
+    let mut procedural_bones = Vec::new();
+
+    for (line_index, line) in text.lines().enumerate() {
+        let line_number = line_index + 1;
+        let line = line.split("//").next().unwrap_or_default();
+        let mut arguments = line.split_whitespace();
+
+        let Some(command) = arguments.next() else {
+            continue;
+        };
+
+        match command.to_lowercase().as_str() {
+            "<helper>" => {
+                // The parent of the control bone is only used by the modeling program to find the control bone.
+                let [bone, parent, _control_parent, control] = parse_names(&mut arguments, line_number)?;
+                procedural_bones.push(ProceduralBone::Quaternion(QuaternionProceduralBone {
+                    bone,
+                    parent,
+                    control,
+                    ..Default::default()
+                }));
+            }
+            "<aimconstraint>" => {
+                let [bone, parent, aim] = parse_names(&mut arguments, line_number)?;
+                procedural_bones.push(ProceduralBone::AimAt(AimAtProceduralBone {
+                    bone,
+                    parent,
+                    aim,
+                    ..Default::default()
+                }));
+            }
+            "<basepos>" => {
+                let base_position = parse_vector(&mut arguments, line_number)?;
+                match procedural_bones.last_mut() {
+                    Some(ProceduralBone::Quaternion(quaternion_bone)) => quaternion_bone.base_position = base_position,
+                    Some(ProceduralBone::AimAt(aim_at_bone)) => aim_at_bone.base_position = base_position,
+                    None => return Err(ParseVRDError::NoProceduralBone(String::from(command), line_number)),
+                }
+            }
+            "<trigger>" => {
+                let tolerance = parse_number(&mut arguments, line_number)?;
+                let trigger = parse_vector(&mut arguments, line_number)?;
+                let rotation = parse_vector(&mut arguments, line_number)?;
+                let position = parse_vector(&mut arguments, line_number)?;
+                match procedural_bones.last_mut() {
+                    Some(ProceduralBone::Quaternion(quaternion_bone)) => quaternion_bone.triggers.push(ProceduralTrigger {
+                        tolerance,
+                        trigger,
+                        rotation,
+                        position,
+                    }),
+                    _ => return Err(ParseVRDError::NoProceduralBone(String::from(command), line_number)),
+                }
+            }
+            "<aimvector>" | "<upvector>" => {
+                let vector = parse_vector(&mut arguments, line_number)?;
+                match procedural_bones.last_mut() {
+                    Some(ProceduralBone::AimAt(aim_at_bone)) if command.eq_ignore_ascii_case("<aimvector>") => aim_at_bone.aim_vector = vector,
+                    Some(ProceduralBone::AimAt(aim_at_bone)) => aim_at_bone.up_vector = vector,
+                    _ => return Err(ParseVRDError::NoProceduralBone(String::from(command), line_number)),
+                }
+            }
+            // These only change how the helper is shown in the modeling program.
+            "<display>" | "<rotateaxis>" | "<jointorient>" => continue,
+            _ => return Err(ParseVRDError::UnknownCommand(String::from(command), line_number)),
+        }
+
+        if let Some(argument) = arguments.next() {
+            return Err(ParseVRDError::UnexpectedArgument(String::from(argument), line_number));
+        }
+    }
+
+    Ok(procedural_bones)
+}
+
+fn parse_names<'a, const COUNT: usize>(arguments: &mut impl Iterator<Item = &'a str>, line_number: usize) -> Result<[String; COUNT], ParseVRDError> {
+    let mut names = [const { String::new() }; COUNT];
+    for name in &mut names {
+        *name = arguments.next().map(String::from).ok_or(ParseVRDError::ExpectedBoneName(line_number))?;
+    }
+    Ok(names)
+}
+
+fn parse_number<'a>(arguments: &mut impl Iterator<Item = &'a str>, line_number: usize) -> Result<f64, ParseVRDError> {
+    arguments
+        .next()
+        .and_then(|argument| argument.parse().ok())
+        .ok_or(ParseVRDError::ExpectedNumber(line_number))
+}
+
+fn parse_vector<'a>(arguments: &mut impl Iterator<Item = &'a str>, line_number: usize) -> Result<Vector3, ParseVRDError> {
+    Ok(Vector3::new(
+        parse_number(arguments, line_number)?,
+        parse_number(arguments, line_number)?,
+        parse_number(arguments, line_number)?,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE_VRD: &str = "\
+// Elbow and bicep helpers
+<helper> Bip01_L_Elbow_Helper Bip01_L_UpperArm Bip01_L_UpperArm Bip01_L_Forearm
+<display> 1.5 3 2 100
+<basepos> 10.5 0 0
+<rotateaxis> 0 0 0
+<jointorient> 0 0 0
+<trigger> 90 0 0 0 0 0 0 0 0 0
+<trigger> 90 0 0 -90 0 0 -45 0 0 0
+
+<aimconstraint> Bip01_Spine_Helper Bip01_Spine Bip01_Neck
+<aimvector> 1 0 0
+<upvector> 0 1 0
+<basepos> 0 0 2.25 // Offset from the parent
+";
+
+    #[test]
+    fn parses_helper_with_control_bone() {
+        let procedural_bones = parse_vrd(SAMPLE_VRD).unwrap();
+        assert_eq!(procedural_bones.len(), 2);
+
+        let ProceduralBone::Quaternion(helper) = &procedural_bones[0] else {
+            panic!("First bone should be a quaternion bone");
+        };
+        assert_eq!(helper.bone, "Bip01_L_Elbow_Helper");
+        assert_eq!(helper.parent, "Bip01_L_UpperArm");
+        assert_eq!(helper.control, "Bip01_L_Forearm");
+        assert_eq!(helper.base_position, Vector3::new(10.5, 0.0, 0.0));
+        assert_eq!(helper.triggers.len(), 2);
+        assert_eq!(helper.triggers[1].tolerance, 90.0);
+        assert_eq!(helper.triggers[1].trigger, Vector3::new(0.0, 0.0, -90.0));
+        assert_eq!(helper.triggers[1].rotation, Vector3::new(0.0, 0.0, -45.0));
+    }
+
+    #[test]
+    fn parses_aim_constraint() {
+        let procedural_bones = parse_vrd(SAMPLE_VRD).unwrap();
+
+        let ProceduralBone::AimAt(aim_at) = &procedural_bones[1] else {
+            panic!("Second bone should be an aim at bone");
+        };
+        assert_eq!(aim_at.bone, "Bip01_Spine_Helper");
+        assert_eq!(aim_at.parent, "Bip01_Spine");
+        assert_eq!(aim_at.aim, "Bip01_Neck");
+        assert_eq!(aim_at.aim_vector, Vector3::X);
+        assert_eq!(aim_at.up_vector, Vector3::Y);
+        assert_eq!(aim_at.base_position, Vector3::new(0.0, 0.0, 2.25));
+    }
+
+    #[test]
+    fn ignores_case_comments_and_blank_lines() {
+        assert!(parse_vrd("").unwrap().is_empty());
+        assert!(parse_vrd("// Only a comment\n\n   \n").unwrap().is_empty());
+
+        let procedural_bones = parse_vrd("<HELPER> Helper Parent ControlParent Control // The elbow\n<BasePos> 1 2 3").unwrap();
+        let [ProceduralBone::Quaternion(helper)] = procedural_bones.as_slice() else {
+            panic!("Should parse a single quaternion bone");
+        };
+        assert_eq!(helper.control, "Control");
+        assert_eq!(helper.base_position, Vector3::new(1.0, 2.0, 3.0));
+    }
+
+    #[test]
+    fn rejects_extra_numbers() {
+        let result = parse_vrd("<aimconstraint> Bone Parent Aim\n<aimvector> 1 0 0 0");
+        assert!(matches!(result, Err(ParseVRDError::UnexpectedArgument(argument, 2)) if argument == "0"));
+    }
+
+    #[test]
+    fn rejects_helper_with_missing_name() {
+        let result = parse_vrd("<helper> Helper Parent ControlParent");
+        assert!(matches!(result, Err(ParseVRDError::ExpectedBoneName(1))));
+    }
+
+    #[test]
+    fn rejects_helper_with_extra_names() {
+        let result = parse_vrd("<helper> Helper Parent ControlParent Control Extra");
+        assert!(matches!(result, Err(ParseVRDError::UnexpectedArgument(argument, 1)) if argument == "Extra"));
+    }
+
+    #[test]
+    fn rejects_malformed_lines() {
+        assert!(matches!(parse_vrd("<basepos> 0 0 0"), Err(ParseVRDError::NoProceduralBone(_, 1))));
+        assert!(matches!(parse_vrd("<unknown> 0"), Err(ParseVRDError::UnknownCommand(_, 1))));
+        assert!(matches!(
+            parse_vrd("<helper> Helper Parent ControlParent Control\n<trigger> 90 0 zero 0 0 0 0 0 0 0"),
+            Err(ParseVRDError::ExpectedNumber(2))
+        ));
+    }
+}
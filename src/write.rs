@@ -4,6 +4,7 @@ use std::fs::write;
 use thiserror::Error as ThisError;
 
 use crate::{
+    info,
     process::{self, CompiledData, FLOAT_TOLERANCE, MAX_HARDWARE_BONES_PER_STRIP, VERTEX_CACHE_SIZE},
     utilities::mathematics::{EULER_ROTATION, Quaternion, Vector2, Vector3, Vector4},
 };
@@ -270,16 +271,22 @@ impl FileWriter {
 }
 
 pub fn write_files(file_name: String, model_name: String, compiled_data: CompiledData, export_path: String) -> Result<(), FileWriteError> {
+    let model_properties = compiled_data.model_properties;
     let mut mdl_header = model::Header {
         version: model::HeaderVersions::TwentyThirteen,
+        eye_position: model_properties.eye_position,
         hull: compiled_data.model_data.bounding_box, // TODO: If the model has no mesh use sequence bounding box.
-        illumination_position: compiled_data.model_data.bounding_box.center(), // TODO: If input, use the input value.
-        flags: model::HeaderFlags::FORCE_OPAQUE
-            | model::HeaderFlags::AUTO_GENERATED_HITBOX
+        illumination_position: model_properties
+            .illumination_position
+            .unwrap_or_else(|| compiled_data.model_data.bounding_box.center()),
+        flags: model::HeaderFlags::from_bits_truncate(model_properties.flags.bits())
             | model::HeaderFlags::FIXED_POINT_FLEXES
             | model::HeaderFlags::VERT_ANIM_FIXED_POINT_SCALE,
-        surface_property: String::from("default"),
-        contents: model::HeaderContents::SOLID,
+        surface_property: model_properties.surface_property.clone(),
+        contents: model::HeaderContents::from_bits_truncate(model_properties.contents.bits()),
+        mass: model_properties.mass as f32,
+        constant_directional_light_dot: model_properties.constant_directional_light_dot,
+        keyvalues: model_properties.keyvalues.clone(),
         second_header: model::SecondHeader {
             name: model_name,
             ..Default::default()
@@ -287,8 +294,13 @@ pub fn write_files(file_name: String, model_name: String, compiled_data: Compile
         ..Default::default()
     };
 
+    let animation_block_size = compiled_data.animation_data.animation_block_size;
+    if animation_block_size.is_some() {
+        mdl_header.animation_block_name = format!("models/{}.ani", mdl_header.second_header.name.trim_end_matches(".mdl"));
+    }
+
     for (bone_index, (bone_name, processed_bone)) in compiled_data.bone_data.processed_bones.into_iter().enumerate() {
-        let bone = model::Bone {
+        let mut bone = model::Bone {
             name: bone_name,
             parent: match processed_bone.parent {
                 Some(index) => index as i32,
@@ -302,16 +314,73 @@ pub fn write_files(file_name: String, model_name: String, compiled_data: Compile
             animation_rotation_scale: compiled_data.animation_data.animation_scales[bone_index].1,
             pose: processed_bone.world_transform.inverse(),
             flags: model::BoneFlags::from_bits_truncate(processed_bone.flags.bits()),
+            procedural: processed_bone.procedural.map(|procedural| match procedural {
+                process::ProceduralBone::Jiggle(jiggle_bone) => model::ProceduralBone::Jiggle(model::JiggleBone {
+                    flags: model::JiggleFlags::from_bits_truncate(jiggle_bone.flags.bits()),
+                    length: jiggle_bone.length as f32,
+                    tip_mass: jiggle_bone.tip_mass as f32,
+                    yaw_stiffness: jiggle_bone.yaw_stiffness as f32,
+                    yaw_damping: jiggle_bone.yaw_damping as f32,
+                    pitch_stiffness: jiggle_bone.pitch_stiffness as f32,
+                    pitch_damping: jiggle_bone.pitch_damping as f32,
+                    along_stiffness: jiggle_bone.along_stiffness as f32,
+                    along_damping: jiggle_bone.along_damping as f32,
+                    angle_limit: jiggle_bone.angle_limit as f32,
+                    minimum_yaw: jiggle_bone.minimum_yaw as f32,
+                    maximum_yaw: jiggle_bone.maximum_yaw as f32,
+                    yaw_friction: jiggle_bone.yaw_friction as f32,
+                    yaw_bounce: jiggle_bone.yaw_bounce as f32,
+                    minimum_pitch: jiggle_bone.minimum_pitch as f32,
+                    maximum_pitch: jiggle_bone.maximum_pitch as f32,
+                    pitch_friction: jiggle_bone.pitch_friction as f32,
+                    pitch_bounce: jiggle_bone.pitch_bounce as f32,
+                    base_mass: jiggle_bone.base_mass as f32,
+                    base_stiffness: jiggle_bone.base_stiffness as f32,
+                    base_damping: jiggle_bone.base_damping as f32,
+                    base_minimum: jiggle_bone.base_minimum,
+                    base_maximum: jiggle_bone.base_maximum,
+                    base_friction: jiggle_bone.base_friction,
+                }),
+                process::ProceduralBone::Quaternion(quaternion_bone) => model::ProceduralBone::QuaternionInterpolation(model::QuaternionInterpolationBone {
+                    control: quaternion_bone.control as i32,
+                    triggers: quaternion_bone
+                        .triggers
+                        .into_iter()
+                        .map(|trigger| model::QuaternionInterpolationTrigger {
+                            inverse_tolerance: trigger.inverse_tolerance as f32,
+                            trigger: trigger.trigger,
+                            position: trigger.position,
+                            quaternion: trigger.rotation,
+                        })
+                        .collect(),
+                    ..Default::default()
+                }),
+                process::ProceduralBone::AimAt(aim_at_bone) => model::ProceduralBone::AimAtBone(model::AimAtBone {
+                    parent: aim_at_bone.parent as i32,
+                    aim: aim_at_bone.aim as i32,
+                    aim_vector: aim_at_bone.aim_vector,
+                    up_vector: aim_at_bone.up_vector,
+                    base_position: aim_at_bone.base_position,
+                }),
+            }),
             physics_bone: -1,
-            surface_property: String::from("default"),
-            contents: model::HeaderContents::SOLID,
+            surface_property: model_properties.surface_property.clone(),
+            contents: model::HeaderContents::from_bits_truncate(model_properties.contents.bits()),
             ..Default::default()
         };
+
+        // Every bone is saved in the zero frames of the animations.
+        if animation_block_size.is_some() {
+            bone.flags |= model::BoneFlags::HAS_SAVE_FRAME_POSITION | model::BoneFlags::HAS_SAVE_FRAME_ROTATION;
+        }
+
         mdl_header.bones.push(bone);
     }
 
     mdl_header.bone_table_by_name = compiled_data.bone_data.sorted_bones_by_name;
 
+    write_bone_controllers(compiled_data.bone_data.bone_controllers, &mut mdl_header);
+
     write_ik_chains(compiled_data.bone_data.ik_chains, &mut mdl_header);
 
     let mut hitbox_set = model::HitboxSet {
@@ -332,16 +401,103 @@ pub fn write_files(file_name: String, model_name: String, compiled_data: Compile
 
     write_animations(compiled_data.animation_data, &mut mdl_header);
 
-    for (processed_sequence_name, processed_sequence) in compiled_data.sequence_data {
+    for (pose_parameter_name, pose_parameter) in compiled_data.pose_parameters {
+        let mut flags = model::PoseParameterFlags::empty();
+        flags.set(model::PoseParameterFlags::LOOPING, pose_parameter.loop_range.is_some());
+
+        mdl_header.pose_parameters.push(model::PoseParameter {
+            name: pose_parameter_name,
+            flags,
+            start: pose_parameter.start as f32,
+            end: pose_parameter.end as f32,
+            loop_range: pose_parameter.loop_range.unwrap_or_default() as f32,
+            ..Default::default()
+        });
+    }
+
+    mdl_header.nodes = compiled_data.sequence_data.nodes.into_iter().collect();
+    mdl_header.node_transitions = compiled_data.sequence_data.node_transitions.into_iter().flatten().collect();
+
+    for (processed_sequence_name, processed_sequence) in compiled_data.sequence_data.processed_sequences {
+        // The sequence always loops and is a delta with its first animation.
+        let first_animation_flags = &mdl_header.animation_descriptions[processed_sequence.animations[0][0] as usize].flags;
+        let mut sequence_flags = model::SequenceDescriptionFlags::from_bits_truncate(processed_sequence.flags.bits());
+        if first_animation_flags.contains(model::AnimationDescriptionFlags::LOOPING) {
+            sequence_flags.insert(model::SequenceDescriptionFlags::LOOPING);
+        }
+        if first_animation_flags.contains(model::AnimationDescriptionFlags::DELTA) {
+            sequence_flags.insert(model::SequenceDescriptionFlags::DELTA);
+        }
+
+        let (activity, activity_weight) = processed_sequence.activity.unwrap_or((String::new(), -1));
+
         let sequence_description = model::SequenceDescription {
             name: processed_sequence_name,
-            activity_weight: -1,
-            fade_in_time: 0.2,
-            fade_out_time: 0.2,
-            blend_size: [processed_sequence.animations.len() as i32, processed_sequence.animations[0].len() as i32],
-            parameter_index: [-1; 2],
+            activity,
+            flags: sequence_flags,
+            activity_weight,
+            fade_in_time: processed_sequence.fade_in_time as f32,
+            fade_out_time: processed_sequence.fade_out_time as f32,
+            entry_node: processed_sequence.entry_node as i32,
+            exit_node: processed_sequence.exit_node as i32,
+            reverse_transition: processed_sequence.reverse_transition,
+            // The event flag is set by the game once named events are indexed, so it is left unset.
+            events: processed_sequence
+                .events
+                .into_iter()
+                .map(|event| model::Event {
+                    cycle: event.cycle as f32,
+                    event: event.id.unwrap_or_default(),
+                    flags: if event.id.is_some() {
+                        model::EventFlags::empty()
+                    } else {
+                        model::EventFlags::NEW_EVENT_STYLE
+                    },
+                    options: event.options,
+                    name: event.name,
+                    ..Default::default()
+                })
+                .collect(),
+            auto_layers: processed_sequence
+                .auto_layers
+                .into_iter()
+                .map(|auto_layer| model::AutoLayer {
+                    sequence: auto_layer.sequence as i16,
+                    pose_parameter: auto_layer.pose_parameter.map_or(0, |pose_parameter| pose_parameter as i16),
+                    flags: model::AutoLayerFlags::from_bits_truncate(auto_layer.flags.bits()),
+                    start: auto_layer.start as f32,
+                    peak: auto_layer.peak as f32,
+                    tail: auto_layer.tail as f32,
+                    end: auto_layer.end as f32,
+                })
+                .collect(),
+            // The first blend axis is the columns of the grid and the second is the rows.
+            blend_size: [processed_sequence.animations[0].len() as i32, processed_sequence.animations.len() as i32],
+            parameter_index: processed_sequence
+                .blend_axes
+                .each_ref()
+                .map(|blend_axis| blend_axis.as_ref().map_or(-1, |blend_axis| blend_axis.parameter as i32)),
+            parameter_start: processed_sequence
+                .blend_axes
+                .each_ref()
+                .map(|blend_axis| blend_axis.as_ref().map_or(0.0, |blend_axis| blend_axis.start as f32)),
+            parameter_end: processed_sequence
+                .blend_axes
+                .each_ref()
+                .map(|blend_axis| blend_axis.as_ref().map_or(0.0, |blend_axis| blend_axis.end as f32)),
             animations: processed_sequence.animations.into_iter().flatten().collect(),
-            weight_list: vec![1.0; mdl_header.bones.len()],
+            weight_list: processed_sequence.weight_list.into_iter().map(|weight| weight as f32).collect(),
+            ik_rule_count: processed_sequence.ik_rule_count as i32,
+            ik_locks: processed_sequence
+                .ik_locks
+                .into_iter()
+                .map(|ik_lock| model::IKLock {
+                    chain: ik_lock.chain,
+                    position_weight: ik_lock.position_weight,
+                    rotation_weight: ik_lock.rotation_weight,
+                    ..Default::default()
+                })
+                .collect(),
             ..Default::default()
         };
 
@@ -362,7 +518,7 @@ pub fn write_files(file_name: String, model_name: String, compiled_data: Compile
         ..Default::default()
     };
 
-    mdl_header.material_paths.push(String::from(""));
+    mdl_header.material_paths.extend(compiled_data.model_data.material_paths);
 
     write_model_flex_data(compiled_data.model_data.flex_data, &mut mdl_header);
 
@@ -378,6 +534,13 @@ pub fn write_files(file_name: String, model_name: String, compiled_data: Compile
 
     mdl_header.material_replacements.push((0..mdl_header.materials.len() as i16).collect());
 
+    let mut ani_writer = None;
+    if let Some(block_size) = animation_block_size {
+        let mut writer = FileWriter::default();
+        mdl_header.write_animation_block_file(&mut writer, block_size)?;
+        ani_writer = Some(writer);
+    }
+
     let mut mdl_writer = FileWriter::default();
     mdl_header.write_data(&mut mdl_writer)?;
     let mut vvd_writer = FileWriter::default();
@@ -386,15 +549,38 @@ pub fn write_files(file_name: String, model_name: String, compiled_data: Compile
     let mut vtx_writer = FileWriter::default();
     vtx_header.checksum = mdl_header.checksum;
     vtx_header.write_data(&mut vtx_writer)?;
+    if let Some(ani_writer) = &mut ani_writer {
+        ani_writer.write_to_integer(mdl_header.animation_block_checksum_index, mdl_header.checksum);
+    }
 
     // // FIXME: This is a temporary solution to write the files.
     let _ = write(format!("{}/{}.{}", export_path, file_name, "mdl"), mdl_writer.buffer);
     let _ = write(format!("{}/{}.{}", export_path, file_name, "vvd"), vvd_writer.buffer);
     let _ = write(format!("{}/{}.{}", export_path, file_name, "dx90.vtx"), vtx_writer.buffer);
+    if let Some(ani_writer) = ani_writer {
+        let _ = write(format!("{}/{}.{}", export_path, file_name, "ani"), ani_writer.buffer);
+    }
 
     Ok(())
 }
 
+fn write_bone_controllers(bone_controllers: Vec<process::BoneController>, header: &mut model::Header) {
+    for (bone_controller_index, bone_controller) in bone_controllers.into_iter().enumerate() {
+        // The bone stores a controller for each axis in the order of the axis flags.
+        let axis = (bone_controller.flags.bits() & 0x3F).trailing_zeros() as usize;
+        header.bones[bone_controller.bone].bone_controller[axis] = bone_controller_index as i32;
+
+        header.bone_controllers.push(model::BoneController {
+            bone: bone_controller.bone as i32,
+            flags: model::BoneControllerFlags::from_bits_truncate(bone_controller.flags.bits()),
+            start: bone_controller.start as f32,
+            end: bone_controller.end as f32,
+            rest: bone_controller.rest,
+            input_field: bone_controller.input_field,
+        });
+    }
+}
+
 fn write_ik_chains(ik_chains: IndexMap<String, process::IKChain>, header: &mut model::Header) {
     for (ik_chain_name, ik_chain) in ik_chains {
         header.ik_chains.push(model::IKChain {
@@ -430,16 +616,78 @@ fn write_ik_chains(ik_chains: IndexMap<String, process::IKChain>, header: &mut m
 
 fn write_animations(animations: process::AnimationData, header: &mut model::Header) {
     for (processed_animation_name, processed_animation) in animations.processed_animations {
+        let is_delta = processed_animation.flags.contains(process::AnimationFlags::DELTA);
+        let movements = processed_animation
+            .movements
+            .into_iter()
+            .map(|movement| model::Movement {
+                end_frame: movement.end_frame as i32,
+                flags: model::MovementFlags::from_bits_truncate(movement.flags.bits()),
+                start_velocity: movement.velocity as f32,
+                end_velocity: movement.velocity as f32,
+                angle: movement.angle as f32,
+                vector: movement.vector,
+                position: movement.position,
+            })
+            .collect();
+        let ik_rules = processed_animation
+            .ik_rules
+            .into_iter()
+            .map(|ik_rule| model::IKRule {
+                kind: match ik_rule.kind {
+                    process::IkRuleKind::Touch => model::IKRuleType::Touch,
+                    process::IkRuleKind::Footstep => model::IKRuleType::Footstep,
+                    process::IkRuleKind::Release => model::IKRuleType::Release,
+                    process::IkRuleKind::Unlatch => model::IKRuleType::Unlatch,
+                },
+                chain: ik_rule.chain as i32,
+                bone: ik_rule.bone.map_or(-1, |bone| bone as i32),
+                height: ik_rule.height as f32,
+                radius: ik_rule.radius as f32,
+                floor: ik_rule.floor as f32,
+                position: ik_rule.position,
+                rotation: ik_rule.rotation,
+                compressed_error: compress_ik_error(&ik_rule.errors),
+                start_frame: ik_rule.start_frame as i32,
+                start: ik_rule.start as f32,
+                peak: ik_rule.peak as f32,
+                tail: ik_rule.tail as f32,
+                end: ik_rule.end as f32,
+                contact: ik_rule.contact as f32,
+                drop: ik_rule.drop as f32,
+                top: ik_rule.top as f32,
+                ..Default::default()
+            })
+            .collect();
         let mut animation_description = model::AnimationDescription {
             name: processed_animation_name,
-            fps: 30.0,
+            fps: processed_animation.frame_rate,
+            flags: model::AnimationDescriptionFlags::from_bits_truncate(processed_animation.flags.bits()),
             frame_count: processed_animation.frame_count as i32,
-            // TODO: section_frame_count should use the imported frame count.
-            section_frame_count: if processed_animation.sections.len() > 1 { 30 } else { 0 },
+            movements,
+            ik_rules,
+            section_frame_count: if processed_animation.sections.len() > 1 {
+                processed_animation.section_frame_count as i32
+            } else {
+                0
+            },
             sections: Vec::with_capacity(processed_animation.sections.len()),
             ..Default::default()
         };
 
+        // The first frame is kept in the model while the animation block is not loaded.
+        if animations.animation_block_size.is_some() {
+            let mut first_section = processed_animation.sections[0].iter().collect::<Vec<_>>();
+            first_section.sort_by_key(|bone_data| bone_data.bone);
+            animation_description.zero_frame_span = processed_animation.frame_count.min(i16::MAX as usize) as i16;
+            animation_description.zero_frames.push(model::ZeroFrame {
+                bones: first_section
+                    .into_iter()
+                    .map(|bone_data| (bone_data.raw_position[0], bone_data.raw_rotation[0]))
+                    .collect(),
+            });
+        }
+
         for mut section in processed_animation.sections {
             let mut animation_section = model::AnimationSection {
                 animation_data: Vec::with_capacity(section.len()),
@@ -448,196 +696,10 @@ fn write_animations(animations: process::AnimationData, header: &mut model::Head
 
             section.sort_by_key(|to| to.bone);
 
-            for animation_bone_data in section {
-                let scale = animations.animation_scales[animation_bone_data.bone as usize].1;
-                let mut scaled_rotation_axis = [
-                    Vec::with_capacity(animation_bone_data.delta_rotation.len()),
-                    Vec::with_capacity(animation_bone_data.delta_rotation.len()),
-                    Vec::with_capacity(animation_bone_data.delta_rotation.len()),
-                ];
-                for rotation in &animation_bone_data.delta_rotation {
-                    let (roll, pitch, yaw) = rotation.to_euler(EULER_ROTATION);
-
-                    scaled_rotation_axis[0].push(if roll.abs() > FLOAT_TOLERANCE { (roll / scale[0]) as i16 } else { 0 });
-                    scaled_rotation_axis[1].push(if pitch.abs() > FLOAT_TOLERANCE { (pitch / scale[1]) as i16 } else { 0 });
-                    scaled_rotation_axis[2].push(if yaw.abs() > FLOAT_TOLERANCE { (yaw / scale[2]) as i16 } else { 0 });
-                }
-
-                let scale = animations.animation_scales[animation_bone_data.bone as usize].0;
-                let mut scaled_position_axis = [
-                    Vec::with_capacity(animation_bone_data.delta_position.len()),
-                    Vec::with_capacity(animation_bone_data.delta_position.len()),
-                    Vec::with_capacity(animation_bone_data.delta_position.len()),
-                ];
-                for position in &animation_bone_data.delta_position {
-                    for axis in 0..3 {
-                        scaled_position_axis[axis].push(if position[axis].abs() > FLOAT_TOLERANCE {
-                            (position[axis] / scale[axis]) as i16
-                        } else {
-                            0
-                        });
-                    }
-                }
-
-                fn encode_run_length(values: &[i16]) -> Vec<model::CompressedAnimationEntry> {
-                    let mut encoding = Vec::new();
-
-                    let mut current_total = 0;
-                    let mut current_valid = Vec::new();
-
-                    for &value in values {
-                        // Check if the current header is full.
-                        if current_total == u8::MAX {
-                            encoding.push(model::CompressedAnimationEntry::Header(model::CompressedAnimationEntryHeader {
-                                total: current_total,
-                                valid: current_valid.len() as u8,
-                            }));
-                            encoding.extend(current_valid.into_iter().map(model::CompressedAnimationEntry::Value));
-                            current_total = 0;
-                            current_valid = Vec::new();
-                        }
-
-                        // Check if the current header is empty.
-                        if current_valid.is_empty() {
-                            current_total += 1;
-                            current_valid.push(value);
-                            continue;
-                        }
-
-                        // Check if the previous value is the same as the current value.
-                        if current_valid[current_valid.len() - 1] == value {
-                            current_total += 1;
-                            continue;
-                        }
-
-                        // If the current value is not the same as the previous value and the values length is not equal to the total.
-                        if current_valid.len() as u8 != current_total {
-                            encoding.push(model::CompressedAnimationEntry::Header(model::CompressedAnimationEntryHeader {
-                                total: current_total,
-                                valid: current_valid.len() as u8,
-                            }));
-                            encoding.extend(current_valid.into_iter().map(model::CompressedAnimationEntry::Value));
-
-                            current_total = 1;
-                            current_valid = vec![value];
-                            continue;
-                        }
-
-                        current_total += 1;
-                        current_valid.push(value);
-                    }
-
-                    encoding.push(model::CompressedAnimationEntry::Header(model::CompressedAnimationEntryHeader {
-                        total: current_total,
-                        valid: current_valid.len() as u8,
-                    }));
-                    encoding.extend(current_valid.into_iter().map(model::CompressedAnimationEntry::Value));
-
-                    encoding
-                }
-
-                let encoded_rotation_axis = [
-                    encode_run_length(&scaled_rotation_axis[0]),
-                    encode_run_length(&scaled_rotation_axis[1]),
-                    encode_run_length(&scaled_rotation_axis[2]),
-                ];
-                let encoded_position_axis = [
-                    encode_run_length(&scaled_position_axis[0]),
-                    encode_run_length(&scaled_position_axis[1]),
-                    encode_run_length(&scaled_position_axis[2]),
-                ];
-
-                let mut rotation = None;
-                let mut position = None;
-
-                if encoded_rotation_axis[0].len() == 2
-                    && encoded_rotation_axis[1].len() == 2
-                    && encoded_rotation_axis[2].len() == 2
-                    && encoded_position_axis[0].len() == 2
-                    && encoded_position_axis[1].len() == 2
-                    && encoded_position_axis[2].len() == 2
-                {
-                    match (&encoded_rotation_axis[0][1], &encoded_rotation_axis[1][1], &encoded_rotation_axis[2][1]) {
-                        (
-                            &model::CompressedAnimationEntry::Value(x),
-                            &model::CompressedAnimationEntry::Value(y),
-                            &model::CompressedAnimationEntry::Value(z),
-                        ) => {
-                            if x != 0 || y != 0 || z != 0 {
-                                rotation = Some(model::AnimationData::Raw(animation_bone_data.raw_rotation[0]));
-                            }
-                        }
-                        _ => {
-                            unreachable!("All the values should be model::FileAnimationEncoding::Value");
-                        }
-                    }
-
-                    match (&encoded_position_axis[0][1], &encoded_position_axis[1][1], &encoded_position_axis[2][1]) {
-                        (
-                            &model::CompressedAnimationEntry::Value(x),
-                            &model::CompressedAnimationEntry::Value(y),
-                            &model::CompressedAnimationEntry::Value(z),
-                        ) => {
-                            if x != 0 || y != 0 || z != 0 {
-                                position = Some(model::AnimationData::Raw(animation_bone_data.raw_position[0]));
-                            }
-                        }
-                        _ => {
-                            unreachable!("All the values should be model::FileAnimationEncoding::Value");
-                        }
-                    }
-                } else {
-                    let mut animation_axis = model::CompressedAnimation::default();
-
-                    let [x_encoded, y_encoded, z_encoded] = encoded_rotation_axis;
-
-                    if x_encoded.len() > 2 || matches!(&x_encoded[1], &model::CompressedAnimationEntry::Value(x) if x != 0) {
-                        animation_axis.values[0] = Some(x_encoded);
-                    }
-
-                    if y_encoded.len() > 2 || matches!(&y_encoded[1], &model::CompressedAnimationEntry::Value(y) if y != 0) {
-                        animation_axis.values[1] = Some(y_encoded);
-                    }
-
-                    if z_encoded.len() > 2 || matches!(&z_encoded[1], &model::CompressedAnimationEntry::Value(z) if z != 0) {
-                        animation_axis.values[2] = Some(z_encoded);
-                    }
-
-                    if animation_axis.values[0].is_some() || animation_axis.values[1].is_some() || animation_axis.values[2].is_some() {
-                        rotation = Some(model::AnimationData::Compressed(animation_axis));
-                    }
-
-                    let mut animation_axis = model::CompressedAnimation::default();
-
-                    let [x_encoded, y_encoded, z_encoded] = encoded_position_axis;
-
-                    if x_encoded.len() > 2 || matches!(&x_encoded[1], &model::CompressedAnimationEntry::Value(x) if x != 0) {
-                        animation_axis.values[0] = Some(x_encoded);
-                    }
-
-                    if y_encoded.len() > 2 || matches!(&y_encoded[1], &model::CompressedAnimationEntry::Value(y) if y != 0) {
-                        animation_axis.values[1] = Some(y_encoded);
-                    }
-
-                    if z_encoded.len() > 2 || matches!(&z_encoded[1], &model::CompressedAnimationEntry::Value(z) if z != 0) {
-                        animation_axis.values[2] = Some(z_encoded);
-                    }
-
-                    if animation_axis.values[0].is_some() || animation_axis.values[1].is_some() || animation_axis.values[2].is_some() {
-                        position = Some(model::AnimationData::Compressed(animation_axis));
-                    }
+            for animation_bone_data in &section {
+                if let Some(animation) = create_animation(animation_bone_data, animations.animation_scales[animation_bone_data.bone as usize], is_delta) {
+                    animation_section.animation_data.push(animation);
                 }
-
-                if rotation.is_none() && position.is_none() {
-                    continue;
-                }
-
-                animation_section.animation_data.push(model::Animation {
-                    bone: animation_bone_data.bone,
-                    position,
-                    rotation,
-                    ..Default::default()
-                });
             }
 
             if animation_section.animation_data.is_empty() {
@@ -650,6 +712,27 @@ fn write_animations(animations: process::AnimationData, header: &mut model::Head
             animation_description.sections.push(animation_section);
         }
 
+        if let (Some(uncompressed_sections), Some(uncompressed_scales)) = (processed_animation.uncompressed_sections, &animations.uncompressed_animation_scales)
+        {
+            let uncompressed_size = uncompressed_sections
+                .iter()
+                .flatten()
+                .filter_map(|animation_bone_data| create_animation(animation_bone_data, uncompressed_scales[animation_bone_data.bone as usize], is_delta))
+                .map(|animation| animation.size())
+                .sum::<usize>();
+            let compressed_size = animation_description
+                .sections
+                .iter()
+                .flat_map(|section| &section.animation_data)
+                .filter(|animation| animation.bone != u8::MAX)
+                .map(|animation| animation.size())
+                .sum::<usize>();
+            info!(
+                "Compressed animation \"{}\" from {} to {} bytes.",
+                animation_description.name, uncompressed_size, compressed_size
+            );
+        }
+
         header.animation_descriptions.push(animation_description);
     }
 }
@@ -871,3 +954,219 @@ fn compute_flex_scale(processed_model_groups: &IndexMap<String, process::ModelGr
 
     max_value / i16::MAX as f64
 }
+
+/// Encodes the animated data of a bone, None if the bone does not move from its rest pose.
+fn create_animation(animation_bone_data: &process::AnimatedBoneData, animation_scales: (Vector3, Vector3), is_delta: bool) -> Option<model::Animation> {
+    let scale = animation_scales.1;
+    let mut scaled_rotation_axis = [
+        Vec::with_capacity(animation_bone_data.delta_rotation.len()),
+        Vec::with_capacity(animation_bone_data.delta_rotation.len()),
+        Vec::with_capacity(animation_bone_data.delta_rotation.len()),
+    ];
+    for rotation in &animation_bone_data.delta_rotation {
+        let (roll, pitch, yaw) = rotation.to_euler(EULER_ROTATION);
+
+        scaled_rotation_axis[0].push(if roll.abs() > FLOAT_TOLERANCE { (roll / scale[0]) as i16 } else { 0 });
+        scaled_rotation_axis[1].push(if pitch.abs() > FLOAT_TOLERANCE { (pitch / scale[1]) as i16 } else { 0 });
+        scaled_rotation_axis[2].push(if yaw.abs() > FLOAT_TOLERANCE { (yaw / scale[2]) as i16 } else { 0 });
+    }
+
+    let scale = animation_scales.0;
+    let mut scaled_position_axis = [
+        Vec::with_capacity(animation_bone_data.delta_position.len()),
+        Vec::with_capacity(animation_bone_data.delta_position.len()),
+        Vec::with_capacity(animation_bone_data.delta_position.len()),
+    ];
+    for position in &animation_bone_data.delta_position {
+        for axis in 0..3 {
+            scaled_position_axis[axis].push(if position[axis].abs() > FLOAT_TOLERANCE {
+                (position[axis] / scale[axis]) as i16
+            } else {
+                0
+            });
+        }
+    }
+
+    let encoded_rotation_axis = [
+        encode_run_length(&scaled_rotation_axis[0]),
+        encode_run_length(&scaled_rotation_axis[1]),
+        encode_run_length(&scaled_rotation_axis[2]),
+    ];
+    let encoded_position_axis = [
+        encode_run_length(&scaled_position_axis[0]),
+        encode_run_length(&scaled_position_axis[1]),
+        encode_run_length(&scaled_position_axis[2]),
+    ];
+
+    let mut rotation = None;
+    let mut position = None;
+
+    if encoded_rotation_axis[0].len() == 2
+        && encoded_rotation_axis[1].len() == 2
+        && encoded_rotation_axis[2].len() == 2
+        && encoded_position_axis[0].len() == 2
+        && encoded_position_axis[1].len() == 2
+        && encoded_position_axis[2].len() == 2
+    {
+        match (&encoded_rotation_axis[0][1], &encoded_rotation_axis[1][1], &encoded_rotation_axis[2][1]) {
+            (&model::CompressedAnimationEntry::Value(x), &model::CompressedAnimationEntry::Value(y), &model::CompressedAnimationEntry::Value(z)) => {
+                if x != 0 || y != 0 || z != 0 {
+                    rotation = Some(model::AnimationData::Raw(animation_bone_data.raw_rotation[0]));
+                }
+            }
+            _ => {
+                unreachable!("All the values should be model::FileAnimationEncoding::Value");
+            }
+        }
+
+        match (&encoded_position_axis[0][1], &encoded_position_axis[1][1], &encoded_position_axis[2][1]) {
+            (&model::CompressedAnimationEntry::Value(x), &model::CompressedAnimationEntry::Value(y), &model::CompressedAnimationEntry::Value(z)) => {
+                if x != 0 || y != 0 || z != 0 {
+                    position = Some(model::AnimationData::Raw(animation_bone_data.raw_position[0]));
+                }
+            }
+            _ => {
+                unreachable!("All the values should be model::FileAnimationEncoding::Value");
+            }
+        }
+    } else {
+        let mut animation_axis = model::CompressedAnimation::default();
+
+        let [x_encoded, y_encoded, z_encoded] = encoded_rotation_axis;
+
+        if x_encoded.len() > 2 || matches!(&x_encoded[1], &model::CompressedAnimationEntry::Value(x) if x != 0) {
+            animation_axis.values[0] = Some(x_encoded);
+        }
+
+        if y_encoded.len() > 2 || matches!(&y_encoded[1], &model::CompressedAnimationEntry::Value(y) if y != 0) {
+            animation_axis.values[1] = Some(y_encoded);
+        }
+
+        if z_encoded.len() > 2 || matches!(&z_encoded[1], &model::CompressedAnimationEntry::Value(z) if z != 0) {
+            animation_axis.values[2] = Some(z_encoded);
+        }
+
+        if animation_axis.values[0].is_some() || animation_axis.values[1].is_some() || animation_axis.values[2].is_some() {
+            rotation = Some(model::AnimationData::Compressed(animation_axis));
+        }
+
+        let mut animation_axis = model::CompressedAnimation::default();
+
+        let [x_encoded, y_encoded, z_encoded] = encoded_position_axis;
+
+        if x_encoded.len() > 2 || matches!(&x_encoded[1], &model::CompressedAnimationEntry::Value(x) if x != 0) {
+            animation_axis.values[0] = Some(x_encoded);
+        }
+
+        if y_encoded.len() > 2 || matches!(&y_encoded[1], &model::CompressedAnimationEntry::Value(y) if y != 0) {
+            animation_axis.values[1] = Some(y_encoded);
+        }
+
+        if z_encoded.len() > 2 || matches!(&z_encoded[1], &model::CompressedAnimationEntry::Value(z) if z != 0) {
+            animation_axis.values[2] = Some(z_encoded);
+        }
+
+        if animation_axis.values[0].is_some() || animation_axis.values[1].is_some() || animation_axis.values[2].is_some() {
+            position = Some(model::AnimationData::Compressed(animation_axis));
+        }
+    }
+
+    if rotation.is_none() && position.is_none() {
+        return None;
+    }
+
+    Some(model::Animation {
+        bone: animation_bone_data.bone,
+        delta: is_delta,
+        position,
+        rotation,
+        ..Default::default()
+    })
+}
+
+/// Compresses the error of an ik rule, each channel is scaled to fit the range of a short.
+fn compress_ik_error(errors: &[(Vector3, Quaternion)]) -> Option<model::CompressedIKError> {
+    if errors.is_empty() {
+        return None;
+    }
+
+    let channels = errors
+        .iter()
+        .map(|(position, rotation)| {
+            let (roll, pitch, yaw) = rotation.to_euler(EULER_ROTATION);
+            [position.x, position.y, position.z, roll, pitch, yaw]
+        })
+        .collect::<Vec<_>>();
+
+    let mut compressed_error = model::CompressedIKError::default();
+    for channel in 0..6 {
+        let maximum = channels.iter().fold(0.0_f64, |maximum, values| maximum.max(values[channel].abs()));
+        if maximum <= FLOAT_TOLERANCE {
+            continue;
+        }
+
+        let scale = maximum / i16::MAX as f64;
+        let scaled_values = channels.iter().map(|values| (values[channel] / scale) as i16).collect::<Vec<_>>();
+        compressed_error.scales[channel] = scale as f32;
+        compressed_error.values[channel] = Some(encode_run_length(&scaled_values));
+    }
+
+    Some(compressed_error)
+}
+
+fn encode_run_length(values: &[i16]) -> Vec<model::CompressedAnimationEntry> {
+    let mut encoding = Vec::new();
+
+    let mut current_total = 0;
+    let mut current_valid = Vec::new();
+
+    for &value in values {
+        // Check if the current header is full.
+        if current_total == u8::MAX {
+            encoding.push(model::CompressedAnimationEntry::Header(model::CompressedAnimationEntryHeader {
+                total: current_total,
+                valid: current_valid.len() as u8,
+            }));
+            encoding.extend(current_valid.into_iter().map(model::CompressedAnimationEntry::Value));
+            current_total = 0;
+            current_valid = Vec::new();
+        }
+
+        // Check if the current header is empty.
+        if current_valid.is_empty() {
+            current_total += 1;
+            current_valid.push(value);
+            continue;
+        }
+
+        // Check if the previous value is the same as the current value.
+        if current_valid[current_valid.len() - 1] == value {
+            current_total += 1;
+            continue;
+        }
+
+        // If the current value is not the same as the previous value and the values length is not equal to the total.
+        if current_valid.len() as u8 != current_total {
+            encoding.push(model::CompressedAnimationEntry::Header(model::CompressedAnimationEntryHeader {
+                total: current_total,
+                valid: current_valid.len() as u8,
+            }));
+            encoding.extend(current_valid.into_iter().map(model::CompressedAnimationEntry::Value));
+
+            current_total = 1;
+            current_valid = vec![value];
+            continue;
+        }
+
+        current_total += 1;
+        current_valid.push(value);
+    }
+
+    encoding.push(model::CompressedAnimationEntry::Header(model::CompressedAnimationEntryHeader {
+        total: current_total,
+        valid: current_valid.len() as u8,
+    }));
+    encoding.extend(current_valid.into_iter().map(model::CompressedAnimationEntry::Value));
+
+    encoding
+}
@@ -23,7 +23,7 @@ pub struct Header {
     pub flags: HeaderFlags,
     pub bones: Vec<Bone>,
     pub bone_index: usize,
-    pub bone_controllers: Vec<()>,
+    pub bone_controllers: Vec<BoneController>,
     pub bone_controller_index: usize,
     pub hitbox_sets: Vec<HitboxSet>,
     pub hitbox_set_index: usize,
@@ -41,7 +41,9 @@ pub struct Header {
     pub body_part_index: usize,
     pub attachments: Vec<()>,
     pub attachment_index: usize,
-    pub nodes: Vec<()>,
+    pub nodes: Vec<String>,
+    /// The next node for every node to every other node, indexed by the from node then the to node.
+    pub node_transitions: Vec<u8>,
     pub node_index: usize,
     pub node_name_index: usize,
     pub flex_descriptions: Vec<FlexDescription>,
@@ -54,7 +56,7 @@ pub struct Header {
     pub ik_chain_index: usize,
     pub mouths: Vec<()>,
     pub mouth_index: usize,
-    pub pose_parameters: Vec<()>,
+    pub pose_parameters: Vec<PoseParameter>,
     pub pose_parameter_index: usize,
     pub surface_property: String,
     pub keyvalues: String,
@@ -65,8 +67,9 @@ pub struct Header {
     pub include_models: Vec<()>,
     pub include_model_index: usize,
     pub animation_block_name: String,
-    pub animation_blocks: Vec<()>,
+    pub animation_blocks: Vec<AnimationBlock>,
     pub animation_block_index: usize,
+    pub animation_block_checksum_index: usize,
     pub bone_table_by_name: Vec<u8>,
     pub bone_table_by_name_index: usize,
     pub constant_directional_light_dot: u8,
@@ -180,14 +183,22 @@ impl Header {
 
         self.write_bones(writer)?;
 
+        self.write_bone_controllers(writer)?;
+
         self.write_hitbox_sets(writer)?;
 
         self.write_bone_table_by_name(writer)?;
 
         self.write_animations(writer)?;
 
+        self.write_animation_blocks(writer)?;
+
         self.write_sequences(writer)?;
 
+        self.write_pose_parameters(writer)?;
+
+        self.write_nodes(writer)?;
+
         self.write_body_parts(writer)?;
 
         self.write_flex_data(writer)?;
@@ -219,6 +230,22 @@ impl Header {
         }
         writer.align(4);
 
+        for bone in &mut self.bones {
+            bone.write_procedural(writer)?;
+        }
+        writer.align(4);
+
+        Ok(())
+    }
+
+    fn write_bone_controllers(&mut self, writer: &mut FileWriter) -> Result<(), FileWriteError> {
+        writer.write_to_integer_offset(self.bone_controller_index, writer.this() - self.this)?;
+
+        for bone_controller in &self.bone_controllers {
+            bone_controller.write_data(writer);
+        }
+        writer.align(4);
+
         Ok(())
     }
 
@@ -256,16 +283,89 @@ impl Header {
         }
         writer.align(4);
 
+        for animation_description in &mut self.animation_descriptions {
+            animation_description.write_movements(writer)?;
+            writer.align(4);
+        }
+
+        for animation_description in &mut self.animation_descriptions {
+            animation_description.write_ik_rules(writer)?;
+            writer.align(4);
+        }
+
         for animation_description in &mut self.animation_descriptions {
             animation_description.write_sections(writer)?;
             writer.align(16);
             animation_description.write_animation_data(writer)?;
             writer.align(4);
+            animation_description.write_zero_frames(writer)?;
+            writer.align(4);
         }
 
         Ok(())
     }
 
+    fn write_animation_blocks(&mut self, writer: &mut FileWriter) -> Result<(), FileWriteError> {
+        if self.animation_blocks.is_empty() {
+            return Ok(());
+        }
+
+        writer.write_to_integer_offset(self.animation_block_index, writer.this() - self.this)?;
+        for animation_block in &mut self.animation_blocks {
+            animation_block.write_data(writer)?;
+        }
+
+        Ok(())
+    }
+
+    /// Writes the animation data to the ani file in blocks no larger than the block size, unless a section is larger.
+    pub fn write_animation_block_file(&mut self, writer: &mut FileWriter, block_size: usize) -> Result<(), FileWriteError> {
+        writer.write_integer(HeaderIdentifier::AnimationBlock.to_integer());
+        writer.write_integer(self.version.to_integer());
+        self.animation_block_checksum_index = writer.write_integer_index();
+        writer.write_char_array(&self.second_header.name, 64);
+        let length_index = writer.write_integer_index();
+        writer.align(16);
+
+        // Block zero is the model file.
+        self.animation_blocks = vec![AnimationBlock::default()];
+
+        let mut block_start = writer.this();
+        for animation_description in &mut self.animation_descriptions {
+            for section in &mut animation_description.sections {
+                let mut section_writer = FileWriter::default();
+                section.write_animation(&mut section_writer)?;
+
+                if writer.this() > block_start && writer.this() - block_start + section_writer.this() > block_size {
+                    self.animation_blocks.push(AnimationBlock {
+                        start: block_start,
+                        end: writer.this(),
+                    });
+                    block_start = writer.this();
+                }
+
+                section.animation_block = self.animation_blocks.len() as i32;
+                section.block_offset = writer.this() - block_start;
+                writer.write_unsigned_byte_array(&section_writer.buffer);
+                writer.align(16);
+            }
+
+            animation_description.animation_block = animation_description.sections[0].animation_block;
+            animation_description.block_offset = animation_description.sections[0].block_offset;
+        }
+
+        if writer.this() > block_start {
+            self.animation_blocks.push(AnimationBlock {
+                start: block_start,
+                end: writer.this(),
+            });
+        }
+
+        writer.write_to_integer_offset(length_index, writer.this())?;
+
+        Ok(())
+    }
+
     fn write_sequences(&mut self, writer: &mut FileWriter) -> Result<(), FileWriteError> {
         writer.write_to_integer_offset(self.sequence_description_index, writer.this() - self.this)?;
 
@@ -274,6 +374,9 @@ impl Header {
         }
 
         for sequence_description in &mut self.sequence_descriptions {
+            sequence_description.write_events(writer)?;
+            sequence_description.write_auto_layers(writer)?;
+            sequence_description.write_ik_locks(writer)?;
             sequence_description.write_animations(writer)?;
             writer.align(4);
             sequence_description.write_bone_weights(writer)?;
@@ -283,6 +386,31 @@ impl Header {
         Ok(())
     }
 
+    fn write_pose_parameters(&mut self, writer: &mut FileWriter) -> Result<(), FileWriteError> {
+        writer.write_to_integer_offset(self.pose_parameter_index, writer.this() - self.this)?;
+
+        for pose_parameter in &mut self.pose_parameters {
+            pose_parameter.write_data(writer);
+        }
+        writer.align(4);
+
+        Ok(())
+    }
+
+    fn write_nodes(&mut self, writer: &mut FileWriter) -> Result<(), FileWriteError> {
+        writer.write_to_integer_offset(self.node_name_index, writer.this() - self.this)?;
+        for node in &self.nodes {
+            writer.write_string_to_table(self.this, node);
+        }
+
+        writer.write_to_integer_offset(self.node_index, writer.this() - self.this)?;
+        debug_assert!(self.node_transitions.len() == self.nodes.len() * self.nodes.len());
+        writer.write_unsigned_byte_array(&self.node_transitions);
+        writer.align(4);
+
+        Ok(())
+    }
+
     fn write_body_parts(&mut self, writer: &mut FileWriter) -> Result<(), FileWriteError> {
         writer.write_to_integer_offset(self.body_part_index, writer.this() - self.this)?;
 
@@ -400,12 +528,14 @@ impl Header {
 pub enum HeaderIdentifier {
     #[default]
     Model,
+    AnimationBlock,
 }
 
 impl HeaderIdentifier {
     fn to_integer(&self) -> i32 {
         match self {
             Self::Model => (84 << 24) + (83 << 16) + (68 << 8) + 73,
+            Self::AnimationBlock => (71 << 24) + (65 << 16) + (68 << 8) + 73,
         }
     }
 }
@@ -582,7 +712,7 @@ pub struct Bone {
     pub pose: Matrix4,
     pub alignment: Quaternion,
     pub flags: BoneFlags,
-    pub procedural: Option<()>,
+    pub procedural: Option<ProceduralBone>,
     pub procedural_index: usize,
     pub physics_bone: i32,
     pub surface_property: String,
@@ -625,7 +755,7 @@ impl Bone {
         debug_assert!(self.alignment.is_finite());
         writer.write_quaternion(self.alignment);
         writer.write_integer(self.flags.bits());
-        writer.write_integer(0); // Procural type
+        writer.write_integer(self.procedural.as_ref().map_or(0, ProceduralBone::to_integer));
         self.procedural_index = writer.write_integer_index();
         writer.write_integer(self.physics_bone);
         writer.write_string_to_table(self.this, &self.surface_property);
@@ -633,6 +763,220 @@ impl Bone {
         writer.write_integer(self.contents.bits());
         writer.write_integer_array(&[0; 8]); // Unused
     }
+
+    fn write_procedural(&mut self, writer: &mut FileWriter) -> Result<(), FileWriteError> {
+        let Some(procedural) = &mut self.procedural else {
+            return Ok(());
+        };
+
+        writer.write_to_integer_offset(self.procedural_index, writer.this() - self.this)?;
+        match procedural {
+            ProceduralBone::Jiggle(jiggle_bone) => jiggle_bone.write_data(writer),
+            ProceduralBone::QuaternionInterpolation(quaternion_bone) => quaternion_bone.write_data(writer)?,
+            ProceduralBone::AimAtBone(aim_at_bone) => aim_at_bone.write_data(writer),
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct BoneController {
+    pub bone: i32,
+    pub flags: BoneControllerFlags,
+    pub start: f32,
+    pub end: f32,
+    pub rest: i32,
+    pub input_field: i32,
+}
+
+impl BoneController {
+    fn write_data(&self, writer: &mut FileWriter) {
+        writer.write_integer(self.bone);
+        writer.write_integer(self.flags.bits());
+        debug_assert!(self.start.is_finite());
+        writer.write_float(self.start);
+        debug_assert!(self.end.is_finite());
+        writer.write_float(self.end);
+        debug_assert!((0..=255).contains(&self.rest));
+        writer.write_integer(self.rest);
+        writer.write_integer(self.input_field);
+        writer.write_integer_array(&[0; 8]); // Unused
+    }
+}
+
+bitflags! {
+    #[derive(Debug, Default)]
+    pub struct BoneControllerFlags: i32 {
+        const X             = 0x0001;
+        const Y             = 0x0002;
+        const Z             = 0x0004;
+        const XR            = 0x0008;
+        const YR            = 0x0010;
+        const ZR            = 0x0020;
+        const ROTATION_LOOP = 0x8000;
+    }
+}
+
+#[derive(Debug)]
+pub enum ProceduralBone {
+    QuaternionInterpolation(QuaternionInterpolationBone),
+    AimAtBone(AimAtBone),
+    Jiggle(JiggleBone),
+}
+
+impl ProceduralBone {
+    fn to_integer(&self) -> i32 {
+        match self {
+            Self::QuaternionInterpolation(_) => 2,
+            Self::AimAtBone(_) => 3,
+            Self::Jiggle(_) => 5,
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct QuaternionInterpolationBone {
+    pub this: usize,
+    pub control: i32,
+    pub triggers: Vec<QuaternionInterpolationTrigger>,
+}
+
+impl QuaternionInterpolationBone {
+    fn write_data(&mut self, writer: &mut FileWriter) -> Result<(), FileWriteError> {
+        self.this = writer.this();
+
+        writer.write_integer(self.control);
+        writer.write_array_size_integer(&self.triggers)?;
+        let trigger_index = writer.write_integer_index();
+
+        writer.write_to_integer_offset(trigger_index, writer.this() - self.this)?;
+        for trigger in &self.triggers {
+            trigger.write_data(writer);
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct QuaternionInterpolationTrigger {
+    pub inverse_tolerance: f32,
+    pub trigger: Quaternion,
+    pub position: Vector3,
+    pub quaternion: Quaternion,
+}
+
+impl QuaternionInterpolationTrigger {
+    fn write_data(&self, writer: &mut FileWriter) {
+        debug_assert!(self.inverse_tolerance.is_finite());
+        writer.write_float(self.inverse_tolerance);
+        debug_assert!(self.trigger.is_finite());
+        writer.write_quaternion(self.trigger);
+        debug_assert!(self.position.is_finite());
+        writer.write_vector3(self.position);
+        debug_assert!(self.quaternion.is_finite());
+        writer.write_quaternion(self.quaternion);
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct AimAtBone {
+    pub parent: i32,
+    pub aim: i32,
+    pub aim_vector: Vector3,
+    pub up_vector: Vector3,
+    pub base_position: Vector3,
+}
+
+impl AimAtBone {
+    fn write_data(&self, writer: &mut FileWriter) {
+        writer.write_integer(self.parent);
+        writer.write_integer(self.aim);
+        debug_assert!(self.aim_vector.is_finite());
+        writer.write_vector3(self.aim_vector);
+        debug_assert!(self.up_vector.is_finite());
+        writer.write_vector3(self.up_vector);
+        debug_assert!(self.base_position.is_finite());
+        writer.write_vector3(self.base_position);
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct JiggleBone {
+    pub flags: JiggleFlags,
+    pub length: f32,
+    pub tip_mass: f32,
+    pub yaw_stiffness: f32,
+    pub yaw_damping: f32,
+    pub pitch_stiffness: f32,
+    pub pitch_damping: f32,
+    pub along_stiffness: f32,
+    pub along_damping: f32,
+    pub angle_limit: f32,
+    pub minimum_yaw: f32,
+    pub maximum_yaw: f32,
+    pub yaw_friction: f32,
+    pub yaw_bounce: f32,
+    pub minimum_pitch: f32,
+    pub maximum_pitch: f32,
+    pub pitch_friction: f32,
+    pub pitch_bounce: f32,
+    pub base_mass: f32,
+    pub base_stiffness: f32,
+    pub base_damping: f32,
+    pub base_minimum: Vector3,
+    pub base_maximum: Vector3,
+    pub base_friction: Vector3,
+}
+
+impl JiggleBone {
+    fn write_data(&mut self, writer: &mut FileWriter) {
+        writer.write_integer(self.flags.bits());
+        writer.write_float_array(&[
+            self.length,
+            self.tip_mass,
+            self.yaw_stiffness,
+            self.yaw_damping,
+            self.pitch_stiffness,
+            self.pitch_damping,
+            self.along_stiffness,
+            self.along_damping,
+            self.angle_limit,
+            self.minimum_yaw,
+            self.maximum_yaw,
+            self.yaw_friction,
+            self.yaw_bounce,
+            self.minimum_pitch,
+            self.maximum_pitch,
+            self.pitch_friction,
+            self.pitch_bounce,
+            self.base_mass,
+            self.base_stiffness,
+            self.base_damping,
+        ]);
+
+        // The base limits are written as left, up and forward.
+        for axis in 0..3 {
+            debug_assert!(self.base_minimum[axis].is_finite() && self.base_maximum[axis].is_finite() && self.base_friction[axis].is_finite());
+            writer.write_float(self.base_minimum[axis] as f32);
+            writer.write_float(self.base_maximum[axis] as f32);
+            writer.write_float(self.base_friction[axis] as f32);
+        }
+    }
+}
+
+bitflags! {
+    #[derive(Debug, Default)]
+    pub struct JiggleFlags: i32 {
+        const FLEXIBLE          = 0x01;
+        const RIGID             = 0x02;
+        const YAW_CONSTRAINT    = 0x04;
+        const PITCH_CONSTRAINT  = 0x08;
+        const ANGLE_CONSTRAINT  = 0x10;
+        const LENGTH_CONSTRAINT = 0x20;
+        const BASE_SPRING       = 0x40;
+    }
 }
 
 bitflags! {
@@ -754,11 +1098,12 @@ pub struct AnimationDescription {
     pub fps: f32,
     pub flags: AnimationDescriptionFlags,
     pub frame_count: i32,
-    pub movements: Vec<()>,
+    pub movements: Vec<Movement>,
     pub movement_index: usize,
     pub animation_block: i32,
     pub animation_index: usize,
-    pub ik_rules: Vec<()>,
+    pub block_offset: usize,
+    pub ik_rules: Vec<IKRule>,
     pub ik_rule_index: usize,
     pub ik_rule_block_index: usize,
     pub local_hierarchies: Vec<()>,
@@ -767,7 +1112,7 @@ pub struct AnimationDescription {
     pub section_index: usize,
     pub section_frame_count: i32,
     pub zero_frame_span: i16,
-    pub zero_frames: Vec<()>,
+    pub zero_frames: Vec<ZeroFrame>,
     pub zero_frame_index: usize,
 }
 
@@ -787,7 +1132,11 @@ impl AnimationDescription {
         writer.write_integer_array(&[0; 6]); // Unused
         debug_assert!(self.animation_block >= 0);
         writer.write_integer(self.animation_block);
-        self.animation_index = writer.write_integer_index();
+        if self.animation_block > 0 {
+            writer.write_integer(self.block_offset as i32);
+        } else {
+            self.animation_index = writer.write_integer_index();
+        }
         writer.write_array_size_integer(&self.ik_rules)?;
         self.ik_rule_index = writer.write_integer_index();
         self.ik_rule_block_index = writer.write_integer_index();
@@ -805,6 +1154,37 @@ impl AnimationDescription {
         Ok(())
     }
 
+    fn write_movements(&mut self, writer: &mut FileWriter) -> Result<(), FileWriteError> {
+        if self.movements.is_empty() {
+            return Ok(());
+        }
+
+        writer.write_to_integer_offset(self.movement_index, writer.this() - self.this)?;
+        for movement in &mut self.movements {
+            movement.write_data(writer);
+        }
+
+        Ok(())
+    }
+
+    fn write_ik_rules(&mut self, writer: &mut FileWriter) -> Result<(), FileWriteError> {
+        if self.ik_rules.is_empty() {
+            return Ok(());
+        }
+
+        // The rules are always kept in the model even if the animation is in an animation block.
+        writer.write_to_integer_offset(self.ik_rule_index, writer.this() - self.this)?;
+        for ik_rule in &mut self.ik_rules {
+            ik_rule.write_data(writer);
+        }
+
+        for ik_rule in &mut self.ik_rules {
+            ik_rule.write_compressed_error(writer)?;
+        }
+
+        Ok(())
+    }
+
     fn write_sections(&mut self, writer: &mut FileWriter) -> Result<(), FileWriteError> {
         debug_assert!(!self.sections.is_empty());
 
@@ -821,6 +1201,11 @@ impl AnimationDescription {
     }
 
     fn write_animation_data(&mut self, writer: &mut FileWriter) -> Result<(), FileWriteError> {
+        // The animation data was written to the ani file.
+        if self.animation_block > 0 {
+            return Ok(());
+        }
+
         writer.write_to_integer_offset(self.animation_index, writer.this() - self.this)?; // This is for crowbar as studioMDL does write to this value but is ignored if sections exist.
 
         if self.sections.len() == 1 {
@@ -836,6 +1221,37 @@ impl AnimationDescription {
 
         Ok(())
     }
+
+    fn write_zero_frames(&mut self, writer: &mut FileWriter) -> Result<(), FileWriteError> {
+        if self.zero_frames.is_empty() {
+            return Ok(());
+        }
+
+        writer.write_to_integer_offset(self.zero_frame_index, writer.this() - self.this)?;
+        for zero_frame in &mut self.zero_frames {
+            zero_frame.write_data(writer);
+        }
+
+        Ok(())
+    }
+}
+
+/// The first frame of an animation kept in the model while the animation block is not loaded.
+#[derive(Debug, Default)]
+pub struct ZeroFrame {
+    /// The position and rotation of every bone, each bone must have the save frame flags.
+    pub bones: Vec<(Vector3, Quaternion)>,
+}
+
+impl ZeroFrame {
+    fn write_data(&mut self, writer: &mut FileWriter) {
+        for (position, rotation) in &self.bones {
+            debug_assert!(position.is_finite());
+            writer.write_vector48(*position);
+            debug_assert!(rotation.is_finite());
+            writer.write_quaternion64(*rotation);
+        }
+    }
 }
 
 bitflags! {
@@ -847,11 +1263,60 @@ bitflags! {
     }
 }
 
+#[derive(Debug, Default)]
+pub struct Movement {
+    pub end_frame: i32,
+    pub flags: MovementFlags,
+    pub start_velocity: f32,
+    pub end_velocity: f32,
+    pub angle: f32,
+    pub vector: Vector3,
+    pub position: Vector3,
+}
+
+impl Movement {
+    fn write_data(&mut self, writer: &mut FileWriter) {
+        debug_assert!(self.end_frame >= 0);
+        writer.write_integer(self.end_frame);
+        writer.write_integer(self.flags.bits());
+        debug_assert!(self.start_velocity.is_finite());
+        writer.write_float(self.start_velocity);
+        debug_assert!(self.end_velocity.is_finite());
+        writer.write_float(self.end_velocity);
+        debug_assert!(self.angle.is_finite());
+        writer.write_float(self.angle);
+        debug_assert!(self.vector.is_finite());
+        writer.write_vector3(self.vector);
+        debug_assert!(self.position.is_finite());
+        writer.write_vector3(self.position);
+    }
+}
+
+bitflags! {
+    #[derive(Debug, Default)]
+    pub struct MovementFlags: i32 {
+        const X             = 0x0001;
+        const Y             = 0x0002;
+        const Z             = 0x0004;
+        const XR            = 0x0008;
+        const YR            = 0x0010;
+        const ZR            = 0x0020;
+        const LX     = 0x0040;
+        const LY     = 0x0080;
+        const LZ     = 0x0100;
+        const LXR    = 0x0200;
+        const LYR    = 0x0400;
+        const LZR    = 0x0800;
+        const LINEAR = 0x1000;
+    }
+}
+
 #[derive(Debug, Default)]
 pub struct AnimationSection {
     pub this: usize,
     pub animation_block: i32,
     pub animation_index: usize,
+    pub block_offset: usize,
     pub animation_data: Vec<Animation>,
 }
 
@@ -861,7 +1326,11 @@ impl AnimationSection {
 
         debug_assert!(self.animation_block >= 0);
         writer.write_integer(self.animation_block);
-        self.animation_index = writer.write_integer_index();
+        if self.animation_block > 0 {
+            writer.write_integer(self.block_offset as i32);
+        } else {
+            self.animation_index = writer.write_integer_index();
+        }
     }
 
     fn write_animation(&mut self, writer: &mut FileWriter) -> Result<(), FileWriteError> {
@@ -879,6 +1348,25 @@ impl AnimationSection {
     }
 }
 
+#[derive(Debug, Default)]
+pub struct AnimationBlock {
+    pub start: usize,
+    pub end: usize,
+}
+
+impl AnimationBlock {
+    fn write_data(&mut self, writer: &mut FileWriter) -> Result<(), FileWriteError> {
+        if self.start > i32::MAX as usize || self.end > i32::MAX as usize {
+            return Err(FileWriteError::IntegerOffsetTooLarge);
+        }
+
+        writer.write_integer(self.start as i32);
+        writer.write_integer(self.end as i32);
+
+        Ok(())
+    }
+}
+
 #[derive(Debug, Default)]
 pub struct Animation {
     pub this: usize,
@@ -890,6 +1378,23 @@ pub struct Animation {
 }
 
 impl Animation {
+    /// Returns the size in bytes the animation is written as.
+    pub fn size(&self) -> usize {
+        let rotation_size = match &self.rotation {
+            Some(AnimationData::Raw(_)) => 8,
+            Some(AnimationData::Compressed(compressed)) => compressed.size(),
+            None => 0,
+        };
+
+        let position_size = match &self.position {
+            Some(AnimationData::Raw(_)) => 6,
+            Some(AnimationData::Compressed(compressed)) => compressed.size(),
+            None => 0,
+        };
+
+        4 + rotation_size + position_size
+    }
+
     fn write_data(&mut self, writer: &mut FileWriter) -> Result<(), FileWriteError> {
         self.this = writer.this();
 
@@ -985,6 +1490,11 @@ pub struct CompressedAnimation {
 }
 
 impl CompressedAnimation {
+    /// Returns the size in bytes of the offsets and the encoded values.
+    fn size(&self) -> usize {
+        6 + self.values.iter().flatten().map(|values| values.len() * 2).sum::<usize>()
+    }
+
     fn write_data(&mut self, writer: &mut FileWriter) {
         self.this = writer.this();
 
@@ -1016,6 +1526,141 @@ impl CompressedAnimation {
     }
 }
 
+#[derive(Debug, Default)]
+pub struct IKRule {
+    pub this: usize,
+    pub kind: IKRuleType,
+    pub chain: i32,
+    pub bone: i32,
+    pub height: f32,
+    pub radius: f32,
+    pub floor: f32,
+    pub position: Vector3,
+    pub rotation: Quaternion,
+    pub compressed_error: Option<CompressedIKError>,
+    pub compressed_error_index: usize,
+    pub start_frame: i32,
+    pub start: f32,
+    pub peak: f32,
+    pub tail: f32,
+    pub end: f32,
+    pub contact: f32,
+    pub drop: f32,
+    pub top: f32,
+    pub attachment: String,
+}
+
+impl IKRule {
+    fn write_data(&mut self, writer: &mut FileWriter) {
+        self.this = writer.this();
+
+        writer.write_integer(0); // Index
+        writer.write_integer(self.kind.to_integer());
+        writer.write_integer(self.chain);
+        writer.write_integer(self.bone);
+        writer.write_integer(self.chain); // Slot
+        debug_assert!(self.height.is_finite());
+        writer.write_float(self.height);
+        debug_assert!(self.radius.is_finite());
+        writer.write_float(self.radius);
+        debug_assert!(self.floor.is_finite());
+        writer.write_float(self.floor);
+        debug_assert!(self.position.is_finite());
+        writer.write_vector3(self.position);
+        debug_assert!(self.rotation.is_finite());
+        writer.write_quaternion(self.rotation);
+        self.compressed_error_index = writer.write_integer_index();
+        writer.write_integer(0); // Unused
+        debug_assert!(self.start_frame >= 0);
+        writer.write_integer(self.start_frame);
+        writer.write_integer(0); // Error Index
+        writer.write_float_array(&[self.start, self.peak, self.tail, self.end]);
+        writer.write_float(0.0); // Unused
+        writer.write_float(self.contact);
+        writer.write_float(self.drop);
+        writer.write_float(self.top);
+        writer.write_integer_array(&[0; 3]); // Unused
+        writer.write_string_to_table(self.this, &self.attachment);
+        writer.write_integer_array(&[0; 7]); // Unused
+    }
+
+    fn write_compressed_error(&mut self, writer: &mut FileWriter) -> Result<(), FileWriteError> {
+        let Some(compressed_error) = &mut self.compressed_error else {
+            return Ok(());
+        };
+
+        writer.write_to_integer_offset(self.compressed_error_index, writer.this() - self.this)?;
+        compressed_error.write_data(writer);
+        compressed_error.write_values(writer)?;
+        writer.align(4);
+
+        Ok(())
+    }
+}
+
+#[derive(Debug, Default)]
+pub enum IKRuleType {
+    Touch,
+    #[default]
+    Footstep,
+    Release,
+    Unlatch,
+}
+
+impl IKRuleType {
+    fn to_integer(&self) -> i32 {
+        match self {
+            Self::Touch => 1,
+            Self::Footstep => 3,
+            Self::Release => 4,
+            Self::Unlatch => 6,
+        }
+    }
+}
+
+/// The position and rotation error of the chain for every frame of an ik rule.
+#[derive(Debug, Default)]
+pub struct CompressedIKError {
+    pub this: usize,
+    pub scales: [f32; 6],
+    pub offsets: [usize; 6],
+    /// The position channels followed by the euler rotation channels.
+    pub values: [Option<Vec<CompressedAnimationEntry>>; 6],
+}
+
+impl CompressedIKError {
+    fn write_data(&mut self, writer: &mut FileWriter) {
+        self.this = writer.this();
+
+        writer.write_float_array(&self.scales);
+        for channel in 0..6 {
+            self.offsets[channel] = writer.write_short_index();
+        }
+    }
+
+    fn write_values(&mut self, writer: &mut FileWriter) -> Result<(), FileWriteError> {
+        for channel in 0..6 {
+            if let Some(values) = &self.values[channel] {
+                writer.write_to_short_offset(self.offsets[channel], writer.this() - self.this)?;
+
+                for value in values {
+                    match value {
+                        CompressedAnimationEntry::Header(header) => {
+                            writer.write_unsigned_byte(header.valid);
+                            writer.write_unsigned_byte(header.total);
+                        }
+                        CompressedAnimationEntry::Value(value) => {
+                            writer.write_short(*value);
+                        }
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
 #[derive(Debug)]
 pub enum CompressedAnimationEntry {
     Header(CompressedAnimationEntryHeader),
@@ -1109,7 +1754,7 @@ pub struct SequenceDescription {
     pub activity: String,
     pub flags: SequenceDescriptionFlags,
     pub activity_weight: i32,
-    pub events: Vec<()>,
+    pub events: Vec<Event>,
     pub event_index: usize,
     pub bounding: BoundingBox,
     pub animations: Vec<i16>,
@@ -1124,13 +1769,13 @@ pub struct SequenceDescription {
     pub exit_node: i32,
     pub reverse_transition: bool,
     pub ik_rule_count: i32,
-    pub auto_layers: Vec<()>,
+    pub auto_layers: Vec<AutoLayer>,
     pub auto_layer_index: usize,
     pub weight_list: Vec<f32>,
     pub weight_list_index: usize,
     pub pose_keys: Vec<()>,
     pub pose_key_index: usize,
-    pub ik_locks: Vec<()>,
+    pub ik_locks: Vec<IKLock>,
     pub ik_lock_index: usize,
     pub keyvalues: String,
     pub cycle_pose: i32,
@@ -1190,6 +1835,40 @@ impl SequenceDescription {
         Ok(())
     }
 
+    fn write_events(&mut self, writer: &mut FileWriter) -> Result<(), FileWriteError> {
+        writer.write_to_integer_offset(self.event_index, writer.this() - self.this)?;
+
+        for event in &mut self.events {
+            event.write_data(writer);
+        }
+
+        Ok(())
+    }
+
+    fn write_auto_layers(&mut self, writer: &mut FileWriter) -> Result<(), FileWriteError> {
+        writer.write_to_integer_offset(self.auto_layer_index, writer.this() - self.this)?;
+
+        for auto_layer in &self.auto_layers {
+            auto_layer.write_data(writer);
+        }
+
+        Ok(())
+    }
+
+    fn write_ik_locks(&mut self, writer: &mut FileWriter) -> Result<(), FileWriteError> {
+        if self.ik_locks.is_empty() {
+            return Ok(());
+        }
+
+        writer.write_to_integer_offset(self.ik_lock_index, writer.this() - self.this)?;
+
+        for ik_lock in &mut self.ik_locks {
+            ik_lock.write_data(writer);
+        }
+
+        Ok(())
+    }
+
     fn write_animations(&mut self, writer: &mut FileWriter) -> Result<(), FileWriteError> {
         writer.write_to_integer_offset(self.animation_index, writer.this() - self.this)?;
 
@@ -1218,12 +1897,107 @@ bitflags! {
         const CYCLE_POSE = 0x0080;
         const REALTIME   = 0x0100;
         const LOCAL      = 0x0200;
+        const HIDDEN     = 0x0400;
         const ACTIVITY   = 0x1000;
         const EVENT      = 0x2000;
         const WORLD      = 0x4000;
     }
 }
 
+#[derive(Debug, Default)]
+pub struct AutoLayer {
+    pub sequence: i16,
+    pub pose_parameter: i16,
+    pub flags: AutoLayerFlags,
+    pub start: f32,
+    pub peak: f32,
+    pub tail: f32,
+    pub end: f32,
+}
+
+impl AutoLayer {
+    fn write_data(&self, writer: &mut FileWriter) {
+        writer.write_short(self.sequence);
+        writer.write_short(self.pose_parameter);
+        writer.write_integer(self.flags.bits());
+        writer.write_float_array(&[self.start, self.peak, self.tail, self.end]);
+    }
+}
+
+bitflags! {
+    #[derive(Debug, Default)]
+    pub struct AutoLayerFlags: i32 {
+        const SPLINE   = 0x0040;
+        const XFADE    = 0x0080;
+        const NO_BLEND = 0x0200;
+        const LOCAL    = 0x1000;
+        const POSE     = 0x4000;
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct Event {
+    pub this: usize,
+    pub cycle: f32,
+    pub event: i32,
+    pub flags: EventFlags,
+    pub options: String,
+    pub name: String,
+}
+
+impl Event {
+    fn write_data(&mut self, writer: &mut FileWriter) {
+        self.this = writer.this();
+
+        debug_assert!((0.0..=1.0).contains(&self.cycle));
+        writer.write_float(self.cycle);
+        writer.write_integer(self.event);
+        writer.write_integer(self.flags.bits());
+        debug_assert!(self.options.len() < 64);
+        writer.write_char_array(&self.options, 64);
+        writer.write_string_to_table(self.this, &self.name);
+    }
+}
+
+bitflags! {
+    #[derive(Debug, Default)]
+    pub struct EventFlags: i32 {
+        const NEW_EVENT_STYLE = 0x0400;
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct PoseParameter {
+    pub this: usize,
+    pub name: String,
+    pub flags: PoseParameterFlags,
+    pub start: f32,
+    pub end: f32,
+    pub loop_range: f32,
+}
+
+impl PoseParameter {
+    fn write_data(&mut self, writer: &mut FileWriter) {
+        self.this = writer.this();
+
+        writer.write_string_to_table(self.this, &self.name);
+        writer.write_integer(self.flags.bits());
+        debug_assert!(self.start.is_finite());
+        writer.write_float(self.start);
+        debug_assert!(self.end.is_finite());
+        writer.write_float(self.end);
+        debug_assert!(self.loop_range.is_finite());
+        writer.write_float(self.loop_range);
+    }
+}
+
+bitflags! {
+    #[derive(Debug, Default)]
+    pub struct PoseParameterFlags: i32 {
+        const LOOPING = 0x0001;
+    }
+}
+
 #[derive(Debug, Default)]
 pub struct BodyPart {
     pub this: usize,